@@ -0,0 +1,257 @@
+//! The `LLVMPY_*` symbols the stub models.
+
+use std::ffi::{c_char, CStr, CString};
+use std::slice;
+
+use llvmlite_types::{LLVMContextRef, LLVMModuleRef};
+
+use crate::ir::{self, Contents};
+use crate::state::{self, Kind};
+
+/// The magic the stub prefixes its bitcode with, the same as real LLVM bitcode.
+const BITCODE_MAGIC: &[u8] = b"BC\xC0\xDE";
+
+pub struct StubContext {
+    // Not zero sized so every context has a unique address.
+    _unique: u8,
+}
+
+#[derive(Clone)]
+pub struct StubModule {
+    pub ir: String,
+    pub name: CString,
+    pub source_file: CString,
+    pub triple: CString,
+    pub data_layout: CString,
+    pub contents: Contents,
+}
+
+// The global context is never disposed, so it lives outside the allocation tracking.
+static GLOBAL_CONTEXT: StubContext = StubContext { _unique: 0 };
+
+pub unsafe fn module<'a>(module: LLVMModuleRef) -> &'a mut StubModule {
+    &mut *(module as *mut StubModule)
+}
+
+fn new_module(ir: String) -> LLVMModuleRef {
+    // LLVM names modules parsed from memory `<string>`.
+    let module = StubModule {
+        contents: ir::scan(&ir),
+        ir,
+        name: CString::from(c"<string>"),
+        source_file: CString::from(c"<string>"),
+        triple: CString::default(),
+        data_layout: CString::default(),
+    };
+    state::alloc_handle(module, Kind::Module) as LLVMModuleRef
+}
+
+#[no_mangle]
+pub extern "C" fn LLVMPY_GetVersionInfo() -> u32 {
+    state::record("LLVMPY_GetVersionInfo");
+    state::version()
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn LLVMPY_CreateString(s: *const c_char) -> *const c_char {
+    state::record("LLVMPY_CreateString");
+    // A scripted failure returns a copy which is not valid UTF-8.
+    if state::take_failure("LLVMPY_CreateString").is_some() {
+        return state::alloc_string(b"\xFF\xFE");
+    }
+    state::alloc_string(CStr::from_ptr(s).to_bytes())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn LLVMPY_CreateByteString(s: *const c_char, len: usize) -> *const c_char {
+    state::record("LLVMPY_CreateByteString");
+    state::alloc_string(slice::from_raw_parts(s as *const u8, len))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn LLVMPY_DisposeString(s: *const c_char) {
+    state::record("LLVMPY_DisposeString");
+    state::free_string(s);
+}
+
+#[no_mangle]
+pub extern "C" fn LLVMPY_GetGlobalContext() -> LLVMContextRef {
+    state::record("LLVMPY_GetGlobalContext");
+    &GLOBAL_CONTEXT as *const StubContext as LLVMContextRef
+}
+
+#[no_mangle]
+pub extern "C" fn LLVMPY_ContextCreate() -> LLVMContextRef {
+    state::record("LLVMPY_ContextCreate");
+    state::alloc_handle(StubContext { _unique: 0 }, Kind::Context) as LLVMContextRef
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn LLVMPY_ContextDispose(ctx: LLVMContextRef) {
+    state::record("LLVMPY_ContextDispose");
+    state::free_handle(ctx as *mut StubContext, Kind::Context);
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn LLVMPY_ParseAssembly(
+    _ctx: LLVMContextRef,
+    ir: *const c_char,
+    out_msg: *mut *const c_char,
+) -> LLVMModuleRef {
+    state::record("LLVMPY_ParseAssembly");
+    if let Some(message) = state::take_failure("LLVMPY_ParseAssembly") {
+        state::write_message(out_msg, &message);
+        return std::ptr::null_mut();
+    }
+
+    let ir = CStr::from_ptr(ir).to_string_lossy().into_owned();
+    new_module(ir)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn LLVMPY_ParseBitcode(
+    _ctx: LLVMContextRef,
+    bitcode: *const c_char,
+    len: usize,
+    out_msg: *mut *const c_char,
+) -> LLVMModuleRef {
+    state::record("LLVMPY_ParseBitcode");
+    if let Some(message) = state::take_failure("LLVMPY_ParseBitcode") {
+        state::write_message(out_msg, &message);
+        return std::ptr::null_mut();
+    }
+
+    let bitcode = slice::from_raw_parts(bitcode as *const u8, len);
+    let Some(ir) = bitcode.strip_prefix(BITCODE_MAGIC) else {
+        state::write_message(out_msg, "Invalid bitcode signature");
+        return std::ptr::null_mut();
+    };
+
+    // The IR is stored after a little endian length, mostly so the
+    // buffer contains NUL bytes the same way real bitcode does.
+    let (len, ir) = ir.split_at(4.min(ir.len()));
+    let len = u32::from_le_bytes(len.try_into().unwrap_or_default()) as usize;
+    if len != ir.len() {
+        state::write_message(out_msg, "Invalid bitcode length");
+        return std::ptr::null_mut();
+    }
+
+    new_module(String::from_utf8_lossy(ir).into_owned())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn LLVMPY_WriteBitcodeToString(
+    m: LLVMModuleRef,
+    out: *mut *const c_char,
+    out_len: *mut usize,
+) {
+    state::record("LLVMPY_WriteBitcodeToString");
+    let ir = module(m).ir.as_bytes();
+
+    let mut bitcode = BITCODE_MAGIC.to_vec();
+    bitcode.extend_from_slice(&(ir.len() as u32).to_le_bytes());
+    bitcode.extend_from_slice(ir);
+
+    *out = state::alloc_string(&bitcode);
+    *out_len = bitcode.len();
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn LLVMPY_PrintModuleToString(m: LLVMModuleRef, out: *mut *const c_char) {
+    state::record("LLVMPY_PrintModuleToString");
+    *out = state::alloc_string(module(m).ir.as_bytes());
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn LLVMPY_VerifyModule(
+    _m: LLVMModuleRef,
+    out_msg: *mut *const c_char,
+) -> i32 {
+    state::record("LLVMPY_VerifyModule");
+    // Modules are valid unless a verifier failure has been scripted.
+    match state::take_failure("LLVMPY_VerifyModule") {
+        Some(message) => {
+            state::write_message(out_msg, &message);
+            1
+        },
+        None => 0,
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn LLVMPY_LinkModules(
+    dest: LLVMModuleRef,
+    src: LLVMModuleRef,
+    out_msg: *mut *const c_char,
+) -> i32 {
+    state::record("LLVMPY_LinkModules");
+    let src_ir = std::mem::take(&mut module(src).ir);
+    // LLVM takes ownership of the source module even when linking fails.
+    state::free_handle(src as *mut StubModule, Kind::Module);
+
+    if let Some(message) = state::take_failure("LLVMPY_LinkModules") {
+        state::write_message(out_msg, &message);
+        return 1;
+    }
+
+    let dest = module(dest);
+    dest.ir.push_str(&src_ir);
+    dest.contents = ir::scan(&dest.ir);
+    0
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn LLVMPY_CloneModule(m: LLVMModuleRef) -> LLVMModuleRef {
+    state::record("LLVMPY_CloneModule");
+    state::alloc_handle(module(m).clone(), Kind::Module) as LLVMModuleRef
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn LLVMPY_DisposeModule(m: LLVMModuleRef) {
+    state::record("LLVMPY_DisposeModule");
+    state::free_handle(m as *mut StubModule, Kind::Module);
+}
+
+// The metadata getters return pointers into the module, the same as LLVM.
+
+#[no_mangle]
+pub unsafe extern "C" fn LLVMPY_GetModuleName(m: LLVMModuleRef) -> *const c_char {
+    state::record("LLVMPY_GetModuleName");
+    module(m).name.as_ptr()
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn LLVMPY_SetModuleName(m: LLVMModuleRef, name: *const c_char) {
+    state::record("LLVMPY_SetModuleName");
+    module(m).name = CStr::from_ptr(name).to_owned();
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn LLVMPY_GetModuleSourceFileName(m: LLVMModuleRef) -> *const c_char {
+    state::record("LLVMPY_GetModuleSourceFileName");
+    module(m).source_file.as_ptr()
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn LLVMPY_GetTarget(m: LLVMModuleRef, out: *mut *const c_char) {
+    state::record("LLVMPY_GetTarget");
+    *out = module(m).triple.as_ptr();
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn LLVMPY_SetTarget(m: LLVMModuleRef, triple: *const c_char) {
+    state::record("LLVMPY_SetTarget");
+    module(m).triple = CStr::from_ptr(triple).to_owned();
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn LLVMPY_GetDataLayout(m: LLVMModuleRef, out: *mut *const c_char) {
+    state::record("LLVMPY_GetDataLayout");
+    *out = module(m).data_layout.as_ptr();
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn LLVMPY_SetDataLayout(m: LLVMModuleRef, layout: *const c_char) {
+    state::record("LLVMPY_SetDataLayout");
+    module(m).data_layout = CStr::from_ptr(layout).to_owned();
+}
@@ -0,0 +1,65 @@
+//! The `llvmlite_stub_*` symbols tests use to script and inspect the stub.
+//!
+//! All state apart from live handles is local to the calling thread.
+
+use std::ffi::{c_char, CStr};
+
+use crate::state::{self, Kind};
+
+unsafe fn to_string(s: *const c_char) -> String {
+    CStr::from_ptr(s).to_string_lossy().into_owned()
+}
+
+/// Clears the calls, scripts and version of the current thread.
+#[no_mangle]
+pub extern "C" fn llvmlite_stub_reset() {
+    state::reset();
+}
+
+/// Returns how many times the current thread called `name`.
+#[no_mangle]
+pub unsafe extern "C" fn llvmlite_stub_calls(name: *const c_char) -> usize {
+    state::calls(&to_string(name))
+}
+
+/// Makes the next call to `name` fail with `message`.
+#[no_mangle]
+pub unsafe extern "C" fn llvmlite_stub_fail_next(name: *const c_char, message: *const c_char) {
+    state::fail_next(to_string(name), to_string(message));
+}
+
+/// Sets the packed version returned by `LLVMPY_GetVersionInfo`.
+#[no_mangle]
+pub extern "C" fn llvmlite_stub_set_version(version: u32) {
+    state::set_version(version);
+}
+
+/// Returns the number of strings created by the current thread which are still live.
+#[no_mangle]
+pub extern "C" fn llvmlite_stub_live_strings() -> usize {
+    state::live(Kind::String)
+}
+
+/// Returns the number of contexts created by the current thread which are still live.
+#[no_mangle]
+pub extern "C" fn llvmlite_stub_live_contexts() -> usize {
+    state::live(Kind::Context)
+}
+
+/// Returns the number of modules created by the current thread which are still live.
+#[no_mangle]
+pub extern "C" fn llvmlite_stub_live_modules() -> usize {
+    state::live(Kind::Module)
+}
+
+/// Returns the number of iterators created by the current thread which are still live.
+#[no_mangle]
+pub extern "C" fn llvmlite_stub_live_iterators() -> usize {
+    state::live(Kind::Iterator)
+}
+
+/// Returns how many times the current thread disposed something that was not live.
+#[no_mangle]
+pub extern "C" fn llvmlite_stub_invalid_frees() -> usize {
+    state::invalid_frees()
+}
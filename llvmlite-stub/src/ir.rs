@@ -0,0 +1,419 @@
+//! Just enough of an IR scanner to give stub modules functions, globals and types.
+//!
+//! Only the shape of the IR is looked at, one line at a time, it is not validated.
+
+use std::collections::BTreeMap;
+use std::ffi::CString;
+
+use llvmlite_types::LLVMValueKind;
+
+/// A value owned by a stub module.
+///
+/// The address of a value is handed out as its `LLVMValueRef`, contents are
+/// never resized once scanned so addresses stay stable.
+#[derive(Clone)]
+pub struct StubValue {
+    pub name: CString,
+    pub kind: LLVMValueKind,
+    /// If a function has no body or a global has no initializer.
+    pub declaration: bool,
+    /// The parameters of a function, or the arguments of a call, which are
+    /// only kept for their attributes.
+    pub arguments: Vec<StubValue>,
+    /// The basic blocks in the body of a function.
+    pub blocks: Vec<StubValue>,
+    /// The instructions in a basic block.
+    pub instructions: Vec<StubValue>,
+    /// The opcode of an instruction, the word after any `%name =`.
+    pub opcode: CString,
+    /// The operands of an instruction. Unlike LLVM these are copies of the
+    /// values they refer to rather than the values themselves.
+    pub operands: Vec<StubValue>,
+    /// The attributes of a function, argument, global or call, one per entry
+    /// in the form `Attribute::getAsString` prints them.
+    pub attributes: Vec<CString>,
+    /// The attributes of a function's or call's return value.
+    pub return_attributes: Vec<CString>,
+    /// The attributes of a function or call grouped the way LLVM's
+    /// `AttributeList` reports them: the function's own set, the return
+    /// value's, then each parameter's, with trailing empty sets dropped.
+    pub attribute_sets: Vec<CString>,
+}
+
+/// A named struct type owned by a stub module.
+#[derive(Clone)]
+pub struct StubType {
+    pub name: CString,
+}
+
+/// The values and types found in a module's IR.
+#[derive(Clone, Default)]
+pub struct Contents {
+    pub functions: Vec<StubValue>,
+    pub globals: Vec<StubValue>,
+    pub struct_types: Vec<StubType>,
+}
+
+/// Scans `ir` for function definitions and declarations, globals, named
+/// struct types and attribute groups.
+///
+/// The body of each definition is split into blocks at its labels, with one
+/// instruction per line. An instruction's operands are its `%` and `@`
+/// references and integer literals, in the order they are written except that
+/// the callee of a call comes last, as it does in LLVM.
+///
+/// Attributes are read from parameters, before a function's body, after a
+/// call's arguments and from `#N` references to groups.
+pub fn scan(ir: &str) -> Contents {
+    let mut contents = Contents::default();
+    let mut groups = BTreeMap::new();
+    let mut in_body = false;
+
+    for line in ir.lines() {
+        if in_body {
+            let line = line.split(';').next().unwrap_or_default().trim();
+            if line.starts_with('}') {
+                in_body = false;
+            } else if !line.is_empty() {
+                let function = contents.functions.last_mut().expect("body without a function");
+                body_line(function, line, &contents.struct_types);
+            }
+        } else if line.starts_with("define ") || line.starts_with("declare ") {
+            if let Some((header, rest)) = line.split_once('@') {
+                let declaration = line.starts_with("declare ");
+                let (params, after) = call_parts(rest);
+                let mut function = value(ident(rest), LLVMValueKind::LLVMFunctionValueKind);
+                function.declaration = declaration;
+                function.arguments = parameters(params);
+                function.attributes = attributes(after);
+                function.return_attributes = return_attributes(header);
+                contents.functions.push(function);
+                in_body = !declaration && line.trim_end().ends_with('{');
+            }
+        } else if let Some(rest) = line.strip_prefix('@') {
+            let mut global = value(ident(rest), LLVMValueKind::LLVMGlobalVariableValueKind);
+            global.declaration = line.contains(" external ");
+            global.attributes = split_top(line, ' ')
+                .into_iter()
+                .filter(|token| token.starts_with('#'))
+                .map(|group| CString::new(group).unwrap_or_default())
+                .collect();
+            contents.globals.push(global);
+        } else if let Some(rest) = line.strip_prefix("attributes ") {
+            if let Some((group, set)) = rest.split_once('=') {
+                let set = set.trim().trim_start_matches('{').trim_end_matches('}');
+                groups.insert(group.trim().to_owned(), attributes(set));
+            }
+        } else if let Some(rest) = line.strip_prefix('%') {
+            if line.contains("= type") {
+                let name = CString::new(ident(rest)).unwrap_or_default();
+                contents.struct_types.push(StubType { name });
+            }
+        }
+    }
+
+    // Functions can be used before they are defined, so `@` operands are only
+    // told apart from globals once every function is known. Likewise groups
+    // can be referenced before they are defined.
+    let functions: Vec<_> = contents.functions.iter().map(|f| f.name.clone()).collect();
+    for function in &mut contents.functions {
+        expand(&mut function.attributes, &groups);
+        function.attribute_sets = function_attribute_sets(function);
+
+        let instructions = function.blocks.iter_mut().flat_map(|b| &mut b.instructions);
+        for instruction in instructions {
+            expand(&mut instruction.attributes, &groups);
+            instruction.attribute_sets = function_attribute_sets(instruction);
+
+            for operand in &mut instruction.operands {
+                if operand.kind == LLVMValueKind::LLVMGlobalVariableValueKind
+                    && functions.contains(&operand.name)
+                {
+                    operand.kind = LLVMValueKind::LLVMFunctionValueKind;
+                }
+            }
+        }
+    }
+    for global in &mut contents.globals {
+        expand(&mut global.attributes, &groups);
+    }
+
+    contents
+}
+
+/// Adds a label or instruction line from the body of `function`.
+fn body_line(function: &mut StubValue, line: &str, struct_types: &[StubType]) {
+    if let Some(label) = line.strip_suffix(':') {
+        let block = value(local(label), LLVMValueKind::LLVMBasicBlockValueKind);
+        function.blocks.push(block);
+        return;
+    }
+
+    // The entry block does not need a label.
+    if function.blocks.is_empty() {
+        let block = value("", LLVMValueKind::LLVMBasicBlockValueKind);
+        function.blocks.push(block);
+    }
+
+    let (name, rest) = match line.strip_prefix('%').and_then(|l| l.split_once('=')) {
+        Some((name, rest)) => (local(ident(name)), rest.trim_start()),
+        None => ("", line),
+    };
+    let (opcode, rest) = rest.split_once(' ').unwrap_or((rest, ""));
+
+    let mut instruction = value(name, LLVMValueKind::LLVMInstructionValueKind);
+    instruction.opcode = CString::new(opcode).unwrap_or_default();
+    instruction.operands = operands(rest, &function.arguments, struct_types);
+    if opcode == "call" {
+        let callee = instruction
+            .operands
+            .iter()
+            .position(|o| o.kind == LLVMValueKind::LLVMGlobalVariableValueKind);
+        if let Some(callee) = callee {
+            let callee = instruction.operands.remove(callee);
+            instruction.operands.push(callee);
+        }
+    }
+    if matches!(opcode, "call" | "invoke") {
+        if let Some((header, callee)) = rest.split_once('@') {
+            let (arguments, after) = call_parts(callee);
+            instruction.arguments = parameters(arguments);
+            instruction.attributes = attributes(after);
+            instruction.return_attributes = return_attributes(header);
+        }
+    }
+
+    let block = function.blocks.last_mut().expect("entry block was added");
+    block.instructions.push(instruction);
+}
+
+/// Reads the operands of an instruction from the text after its opcode.
+fn operands(rest: &str, arguments: &[StubValue], struct_types: &[StubType]) -> Vec<StubValue> {
+    // Attributes with arguments, like `dereferenceable(8)`, are not operands.
+    let rest: Vec<_> = rest
+        .split_whitespace()
+        .filter(|token| !(token.starts_with(|c: char| c.is_ascii_lowercase()) && token.contains('(')))
+        .collect();
+    let rest = rest.join(" ").replace([',', '(', ')', '[', ']', '{', '}'], " ");
+    let mut tokens = rest.split_whitespace().peekable();
+    let mut operands = Vec::new();
+    let mut label = false;
+
+    while let Some(token) = tokens.next() {
+        let after_label = std::mem::replace(&mut label, token == "label");
+        if token == "align" {
+            tokens.next();
+        } else if let Some(name) = token.strip_prefix('@') {
+            operands.push(value(ident(name), LLVMValueKind::LLVMGlobalVariableValueKind));
+        } else if let Some(name) = token.strip_prefix('%') {
+            let name = local(ident(name));
+            if struct_types.iter().any(|t| t.name.as_bytes() == name.as_bytes()) {
+                continue;
+            }
+            let kind = if arguments.iter().any(|a| a.name.as_bytes() == name.as_bytes()) {
+                LLVMValueKind::LLVMArgumentValueKind
+            } else if after_label {
+                LLVMValueKind::LLVMBasicBlockValueKind
+            } else {
+                LLVMValueKind::LLVMInstructionValueKind
+            };
+            operands.push(value(name, kind));
+        } else if token.parse::<i64>().is_ok() && tokens.peek() != Some(&"x") {
+            operands.push(value("", LLVMValueKind::LLVMConstantIntValueKind));
+        }
+    }
+
+    operands
+}
+
+/// Splits the text after a function's `@` sigil into its parameters or
+/// arguments, without their parentheses, and the text following them.
+fn call_parts(rest: &str) -> (&str, &str) {
+    let Some(open) = rest.find('(') else {
+        return ("", "");
+    };
+
+    let mut depth = 0;
+    for (i, c) in rest.char_indices().skip_while(|(i, _)| *i < open) {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return (&rest[open + 1..i], &rest[i + 1..]);
+                }
+            },
+            _ => {},
+        }
+    }
+    (&rest[open + 1..], "")
+}
+
+/// Reads the parameters of a function.
+fn parameters(params: &str) -> Vec<StubValue> {
+    split_top(params, ',')
+        .into_iter()
+        .filter(|param| *param != "...")
+        .map(|param| {
+            // A parameter is a type, any attributes then a name, unnamed
+            // parameters have no name but the type may itself start with `%`.
+            let tokens = split_top(param, ' ');
+            let (name, attrs) = match tokens.split_last() {
+                Some((last, rest)) if last.starts_with('%') && !rest.is_empty() => {
+                    (local(ident(&last[1..])), &rest[1..])
+                },
+                _ => ("", tokens.get(1..).unwrap_or_default()),
+            };
+
+            let mut argument = value(name, LLVMValueKind::LLVMArgumentValueKind);
+            argument.attributes = attributes(&attrs.join(" "));
+            argument
+        })
+        .collect()
+}
+
+/// Reads attributes up to a function's body or an invoke's destinations,
+/// keeping `#N` group references to be expanded once every group is known.
+fn attributes(text: &str) -> Vec<CString> {
+    let mut attributes = Vec::new();
+    let mut tokens = split_top(text, ' ').into_iter();
+
+    while let Some(token) = tokens.next() {
+        if token.starts_with('{') || token == "to" {
+            break;
+        }
+
+        let attribute = if token == "align" {
+            format!("align {}", tokens.next().unwrap_or_default())
+        } else if token.starts_with(['#', '"'])
+            || token.starts_with(|c: char| c.is_ascii_lowercase()) && !token.ends_with("unnamed_addr")
+        {
+            token.to_owned()
+        } else {
+            continue;
+        };
+        attributes.push(CString::new(attribute).unwrap_or_default());
+    }
+
+    attributes
+}
+
+/// Reads the attributes of a function's return value from the part of its
+/// header before the name, e.g. `define internal noundef i32`.
+///
+/// Linkage and other keywords look just like attributes, so only the ones a
+/// return value can have are kept.
+fn return_attributes(header: &str) -> Vec<CString> {
+    const RETURN_ATTRIBUTES: [&str; 8] = [
+        "align",
+        "dereferenceable",
+        "inreg",
+        "noalias",
+        "nonnull",
+        "noundef",
+        "signext",
+        "zeroext",
+    ];
+
+    let mut attributes = attributes(header);
+    attributes.retain(|attribute| {
+        let name = attribute.to_str().unwrap_or_default().split([' ', '(']).next();
+        name.is_some_and(|name| RETURN_ATTRIBUTES.contains(&name))
+    });
+    attributes
+}
+
+/// Replaces references to attribute groups with the attributes in them.
+fn expand(attributes: &mut Vec<CString>, groups: &BTreeMap<String, Vec<CString>>) {
+    *attributes = attributes
+        .iter()
+        .flat_map(|attribute| match groups.get(attribute.to_str().unwrap_or_default()) {
+            Some(group) => group.clone(),
+            None => vec![attribute.clone()],
+        })
+        .collect();
+}
+
+/// Groups the attributes of a function or call, see [StubValue::attribute_sets].
+pub fn function_attribute_sets(function: &StubValue) -> Vec<CString> {
+    let sets = [function.attributes.as_slice(), &function.return_attributes]
+        .into_iter()
+        .chain(function.arguments.iter().map(|a| a.attributes.as_slice()));
+    attribute_sets(sets)
+}
+
+/// Joins each set of attributes the way `AttributeSet::getAsString` does.
+fn attribute_sets<'a>(sets: impl IntoIterator<Item = &'a [CString]>) -> Vec<CString> {
+    let mut sets: Vec<_> = sets
+        .into_iter()
+        .map(|set| {
+            let set: Vec<_> = set.iter().map(|attribute| attribute.to_string_lossy()).collect();
+            CString::new(set.join(" ")).unwrap_or_default()
+        })
+        .collect();
+
+    while sets.last().is_some_and(|set| set.as_bytes().is_empty()) {
+        sets.pop();
+    }
+    sets
+}
+
+/// Splits `text` at each `separator` outside of brackets and quotes.
+fn split_top(text: &str, separator: char) -> Vec<&str> {
+    let mut pieces = Vec::new();
+    let (mut depth, mut quoted, mut start) = (0, false, 0);
+
+    for (i, c) in text.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '(' | '[' | '{' | '<' if !quoted => depth += 1,
+            ')' | ']' | '}' | '>' if !quoted => depth -= 1,
+            _ if c == separator && depth == 0 && !quoted => {
+                pieces.push(text[start..i].trim());
+                start = i + 1;
+            },
+            _ => {},
+        }
+    }
+    pieces.push(text[start..].trim());
+
+    pieces.retain(|piece| !piece.is_empty());
+    pieces
+}
+
+fn value(name: &str, kind: LLVMValueKind) -> StubValue {
+    StubValue {
+        name: CString::new(name).unwrap_or_default(),
+        kind,
+        declaration: false,
+        arguments: Vec::new(),
+        blocks: Vec::new(),
+        instructions: Vec::new(),
+        opcode: CString::default(),
+        operands: Vec::new(),
+        attributes: Vec::new(),
+        return_attributes: Vec::new(),
+        attribute_sets: Vec::new(),
+    }
+}
+
+/// Numbered locals such as `%0` have no name in LLVM.
+fn local(name: &str) -> &str {
+    if name.bytes().all(|b| b.is_ascii_digit()) {
+        ""
+    } else {
+        name
+    }
+}
+
+/// Reads an identifier after its `@` or `%` sigil, which may be quoted.
+fn ident(rest: &str) -> &str {
+    if let Some(quoted) = rest.strip_prefix('"') {
+        return quoted.split('"').next().unwrap_or_default();
+    }
+
+    let end = rest
+        .find(|c: char| !(c.is_alphanumeric() || matches!(c, '_' | '.' | '$' | '-')))
+        .unwrap_or(rest.len());
+    &rest[..end]
+}
@@ -0,0 +1,14 @@
+//! A stand-in for `libllvmlite` used to test the binding layer without LLVM.
+//!
+//! Every symbol the binding requires is exported. The symbols the safe API uses
+//! are modelled closely enough to exercise ownership and error handling, the
+//! rest only record the call. Tests script and inspect the stub through the
+//! `llvmlite_stub_*` functions in [control].
+#![allow(non_snake_case, clippy::missing_safety_doc)]
+
+mod api;
+mod control;
+mod ir;
+mod state;
+mod unmodelled;
+mod values;
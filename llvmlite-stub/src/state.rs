@@ -0,0 +1,156 @@
+use std::cell::{Cell, RefCell};
+use std::collections::BTreeMap;
+use std::ffi::c_char;
+use std::sync::Mutex;
+use std::thread::{self, ThreadId};
+
+/// LLVM 14.0.6, packed the same way as `LLVMPY_GetVersionInfo`.
+pub const DEFAULT_VERSION: u32 = (14 << 16) | 6;
+
+/// The kinds of allocation handed out to the binding.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+    String,
+    Context,
+    Module,
+    Iterator,
+}
+
+struct Allocation {
+    kind: Kind,
+    owner: ThreadId,
+    len: usize,
+}
+
+// Handles may be disposed on a different thread to the one that created them,
+// so allocations are tracked globally and attributed to the creating thread.
+static ALLOCATIONS: Mutex<BTreeMap<usize, Allocation>> = Mutex::new(BTreeMap::new());
+
+// Everything else is per-thread so tests running in parallel do not observe
+// each others calls or scripts.
+thread_local! {
+    static CALLS: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+    static FAILURES: RefCell<BTreeMap<String, String>> = const { RefCell::new(BTreeMap::new()) };
+    static INVALID_FREES: Cell<usize> = const { Cell::new(0) };
+    static VERSION: Cell<u32> = const { Cell::new(DEFAULT_VERSION) };
+}
+
+/// Records a call to the given symbol.
+pub fn record(name: &'static str) {
+    CALLS.with(|calls| calls.borrow_mut().push(name));
+}
+
+/// Returns how many times the current thread called the given symbol.
+pub fn calls(name: &str) -> usize {
+    CALLS.with(|calls| calls.borrow().iter().filter(|call| **call == name).count())
+}
+
+/// Scripts the next call to `name` to fail with `message`.
+pub fn fail_next(name: String, message: String) {
+    FAILURES.with(|failures| failures.borrow_mut().insert(name, message));
+}
+
+/// Takes the scripted failure for `name`, if any.
+pub fn take_failure(name: &str) -> Option<String> {
+    FAILURES.with(|failures| failures.borrow_mut().remove(name))
+}
+
+pub fn version() -> u32 {
+    VERSION.with(|version| version.get())
+}
+
+pub fn set_version(version: u32) {
+    VERSION.with(|v| v.set(version));
+}
+
+pub fn invalid_frees() -> usize {
+    INVALID_FREES.with(|frees| frees.get())
+}
+
+/// Clears every piece of per-thread state.
+pub fn reset() {
+    CALLS.with(|calls| calls.borrow_mut().clear());
+    FAILURES.with(|failures| failures.borrow_mut().clear());
+    INVALID_FREES.with(|frees| frees.set(0));
+    VERSION.with(|version| version.set(DEFAULT_VERSION));
+}
+
+/// Starts tracking a live allocation.
+pub fn track(ptr: usize, kind: Kind, len: usize) {
+    let allocation = Allocation {
+        kind,
+        owner: thread::current().id(),
+        len,
+    };
+    ALLOCATIONS.lock().unwrap().insert(ptr, allocation);
+}
+
+/// Stops tracking an allocation, returning its length.
+///
+/// Disposing something that is not live, or is of the wrong kind,
+/// is counted as an invalid free and returns `None`.
+pub fn untrack(ptr: usize, kind: Kind) -> Option<usize> {
+    let mut allocations = ALLOCATIONS.lock().unwrap();
+    match allocations.get(&ptr) {
+        Some(allocation) if allocation.kind == kind => {
+            allocations.remove(&ptr).map(|allocation| allocation.len)
+        },
+        _ => {
+            INVALID_FREES.with(|frees| frees.set(frees.get() + 1));
+            None
+        },
+    }
+}
+
+/// Returns the number of live allocations of `kind` created by the current thread.
+pub fn live(kind: Kind) -> usize {
+    let owner = thread::current().id();
+    ALLOCATIONS
+        .lock()
+        .unwrap()
+        .values()
+        .filter(|allocation| allocation.kind == kind && allocation.owner == owner)
+        .count()
+}
+
+/// Copies `bytes` into a new NUL terminated allocation, the same as
+/// `LLVMPY_CreateByteString`.
+pub fn alloc_string(bytes: &[u8]) -> *const c_char {
+    let mut buffer = Vec::with_capacity(bytes.len() + 1);
+    buffer.extend_from_slice(bytes);
+    buffer.push(0);
+
+    let len = buffer.len();
+    let ptr = Box::into_raw(buffer.into_boxed_slice()) as *mut u8;
+    track(ptr as usize, Kind::String, len);
+    ptr as *const c_char
+}
+
+/// Frees a string created by [alloc_string].
+pub unsafe fn free_string(ptr: *const c_char) {
+    if let Some(len) = untrack(ptr as usize, Kind::String) {
+        let slice = std::ptr::slice_from_raw_parts_mut(ptr as *mut u8, len);
+        drop(Box::from_raw(slice));
+    }
+}
+
+/// Moves `value` to the heap and tracks it as a live handle.
+pub fn alloc_handle<T>(value: T, kind: Kind) -> *mut T {
+    let ptr = Box::into_raw(Box::new(value));
+    track(ptr as usize, kind, 0);
+    ptr
+}
+
+/// Frees a handle created by [alloc_handle].
+pub unsafe fn free_handle<T>(ptr: *mut T, kind: Kind) {
+    if untrack(ptr as usize, kind).is_some() {
+        drop(Box::from_raw(ptr));
+    }
+}
+
+/// Writes a new error message into an LLVM style out-pointer.
+pub unsafe fn write_message(out: *mut *const c_char, message: &str) {
+    if !out.is_null() {
+        *out = alloc_string(message.as_bytes());
+    }
+}
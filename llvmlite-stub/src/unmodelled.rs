@@ -0,0 +1,117 @@
+//! Symbols the binding requires but the stub does not model.
+//!
+//! Each one records the call and returns zero, which reads as null, false
+//! or `0` depending on the signature the binding expects.
+
+use crate::state;
+
+macro_rules! unmodelled {
+    ($($name:ident),* $(,)?) => {
+        $(
+            #[no_mangle]
+            pub extern "C" fn $name() -> usize {
+                state::record(stringify!($name));
+                0
+            }
+        )*
+    };
+}
+
+unmodelled! {
+    LLVMPY_SetCommandLine,
+    LLVMPY_SearchAddressOfSymbol,
+    LLVMPY_AddSymbol,
+    LLVMPY_LoadLibraryPermanently,
+    LLVMPY_LinkInMCJIT,
+    LLVMPY_DisposeExecutionEngine,
+    LLVMPY_AddModule,
+    LLVMPY_RemoveModule,
+    LLVMPY_FinalizeObject,
+    LLVMPY_CreateMCJITCompiler,
+    LLVMPY_GetGlobalValueAddress,
+    LLVMPY_GetFunctionAddress,
+    LLVMPY_RunStaticConstructors,
+    LLVMPY_RunStaticDestructors,
+    LLVMPY_AddGlobalMapping,
+    LLVMPY_GetExecutionEngineTargetData,
+    LLVMPY_TryAllocateExecutableMemory,
+    LLVMPY_MCJITAddObjectFile,
+    LLVMPY_CreateObjectCache,
+    LLVMPY_DisposeObjectCache,
+    LLVMPY_SetObjectCache,
+    LLVMPY_Shutdown,
+    LLVMPY_CreateObjectFile,
+    LLVMPY_DisposeObjectFile,
+    LLVMPY_GetSections,
+    LLVMPY_DisposeSectionIterator,
+    LLVMPY_MoveToNextSection,
+    LLVMPY_IsSectionIteratorAtEnd,
+    LLVMPY_GetSectionName,
+    LLVMPY_GetSectionAddress,
+    LLVMPY_GetSectionContents,
+    LLVMPY_GetSectionSize,
+    LLVMPY_IsSectionText,
+    LLVMPY_SetTimePasses,
+    LLVMPY_ReportAndResetTimings,
+    LLVMPY_CreatePassManager,
+    LLVMPY_DisposePassManager,
+    LLVMPY_CreateFunctionPassManager,
+    LLVMPY_RunPassManagerWithRemarks,
+    LLVMPY_RunPassManager,
+    LLVMPY_RunFunctionPassManagerWithRemarks,
+    LLVMPY_RunFunctionPassManager,
+    LLVMPY_InitializeFunctionPassManager,
+    LLVMPY_FinalizeFunctionPassManager,
+    LLVMPY_GetProcessTriple,
+    LLVMPY_GetHostCPUFeatures,
+    LLVMPY_GetDefaultTargetTriple,
+    LLVMPY_GetHostCPUName,
+    LLVMPY_GetTripleObjectFormat,
+    LLVMPY_CreateTargetData,
+    LLVMPY_CopyStringRepOfTargetData,
+    LLVMPY_DisposeTargetData,
+    LLVMPY_ABISizeOfType,
+    LLVMPY_OffsetOfElement,
+    LLVMPY_ABISizeOfElementType,
+    LLVMPY_ABIAlignmentOfElementType,
+    LLVMPY_GetTargetFromTriple,
+    LLVMPY_GetTargetName,
+    LLVMPY_GetTargetDescription,
+    LLVMPY_CreateTargetMachine,
+    LLVMPY_DisposeTargetMachine,
+    LLVMPY_GetTargetMachineTriple,
+    LLVMPY_SetTargetMachineAsmVerbosity,
+    LLVMPY_TargetMachineEmitToMemory,
+    LLVMPY_CreateTargetMachineData,
+    LLVMPY_AddAnalysisPasses,
+    LLVMPY_GetBufferStart,
+    LLVMPY_GetBufferSize,
+    LLVMPY_DisposeMemoryBuffer,
+    LLVMPY_PassManagerBuilderCreate,
+    LLVMPY_PassManagerBuilderDispose,
+    LLVMPY_PassManagerBuilderGetOptLevel,
+    LLVMPY_PassManagerBuilderSetOptLevel,
+    LLVMPY_PassManagerBuilderGetSizeLevel,
+    LLVMPY_PassManagerBuilderSetSizeLevel,
+    LLVMPY_PassManagerBuilderGetDisableUnrollLoops,
+    LLVMPY_PassManagerBuilderSetDisableUnrollLoops,
+    LLVMPY_PassManagerBuilderUseInlinerWithThreshold,
+    LLVMPY_PassManagerBuilderPopulateFunctionPassManager,
+    LLVMPY_PassManagerBuilderSetLoopVectorize,
+    LLVMPY_PassManagerBuilderGetLoopVectorize,
+    LLVMPY_PassManagerBuilderSetSLPVectorize,
+    LLVMPY_PassManagerBuilderGetSLPVectorize,
+    LLVMPY_PrintValueToString,
+    LLVMPY_SetValueName,
+    LLVMPY_GetGlobalParent,
+    LLVMPY_TypeOf,
+    LLVMPY_PrintType,
+    LLVMPY_TypeIsPointer,
+    LLVMPY_GetElementType,
+    LLVMPY_SetLinkage,
+    LLVMPY_GetLinkage,
+    LLVMPY_SetVisibility,
+    LLVMPY_GetVisibility,
+    LLVMPY_SetDLLStorageClass,
+    LLVMPY_GetDLLStorageClass,
+}
@@ -0,0 +1,313 @@
+//! The value, type and iterator symbols the stub models.
+
+use std::ffi::{c_char, CStr, CString};
+use std::ptr;
+
+use llvmlite_types::*;
+
+use crate::api::module;
+use crate::ir::{self, StubType, StubValue};
+use crate::state::{self, Kind};
+
+/// Every native iterator is a snapshot of the items' addresses.
+pub struct StubIter {
+    items: Vec<usize>,
+    next: usize,
+}
+
+fn new_iter<T, I>(items: &[T]) -> *mut I {
+    let items = items.iter().map(|item| item as *const T as usize).collect();
+    state::alloc_handle(StubIter { items, next: 0 }, Kind::Iterator) as *mut I
+}
+
+unsafe fn iter_next<I, T>(iter: *mut I) -> *mut T {
+    let iter = &mut *(iter as *mut StubIter);
+    match iter.items.get(iter.next) {
+        Some(item) => {
+            iter.next += 1;
+            *item as *mut T
+        },
+        None => ptr::null_mut(),
+    }
+}
+
+/// Hands out a copy of the next string, which the binding disposes.
+unsafe fn string_next<I>(iter: *mut I) -> *const c_char {
+    let item: *mut CString = iter_next(iter);
+    if item.is_null() {
+        return ptr::null();
+    }
+    state::alloc_string((*item).as_bytes())
+}
+
+unsafe fn dispose_iter<I>(iter: *mut I) {
+    state::free_handle(iter as *mut StubIter, Kind::Iterator);
+}
+
+/// The enum attributes the stub knows, the kind of each is its index plus one
+/// so that zero is left to mean unknown.
+const ENUM_ATTRIBUTES: [&str; 8] = [
+    "alwaysinline",
+    "cold",
+    "inlinehint",
+    "minsize",
+    "noinline",
+    "norecurse",
+    "nounwind",
+    "optsize",
+];
+
+/// Finds the item with the given name, the same as LLVM's named lookups.
+unsafe fn find<T, R>(items: &[T], name: *const c_char, name_of: fn(&T) -> &CStr) -> *mut R {
+    let name = CStr::from_ptr(name);
+    items
+        .iter()
+        .find(|item| name_of(item) == name)
+        .map_or(ptr::null_mut(), |item| item as *const T as *mut R)
+}
+
+unsafe fn value<'a>(v: LLVMValueRef) -> &'a StubValue {
+    &*(v as *const StubValue)
+}
+
+unsafe fn ty<'a>(t: LLVMTypeRef) -> &'a StubType {
+    &*(t as *const StubType)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn LLVMPY_ModuleFunctionsIter(m: LLVMModuleRef) -> LLVMFunctionsIteratorRef {
+    state::record("LLVMPY_ModuleFunctionsIter");
+    new_iter(&module(m).contents.functions)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn LLVMPY_FunctionsIterNext(it: LLVMFunctionsIteratorRef) -> LLVMValueRef {
+    state::record("LLVMPY_FunctionsIterNext");
+    iter_next(it)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn LLVMPY_DisposeFunctionsIter(it: LLVMFunctionsIteratorRef) {
+    state::record("LLVMPY_DisposeFunctionsIter");
+    dispose_iter(it);
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn LLVMPY_ModuleGlobalsIter(m: LLVMModuleRef) -> LLVMGlobalsIteratorRef {
+    state::record("LLVMPY_ModuleGlobalsIter");
+    new_iter(&module(m).contents.globals)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn LLVMPY_GlobalsIterNext(it: LLVMGlobalsIteratorRef) -> LLVMValueRef {
+    state::record("LLVMPY_GlobalsIterNext");
+    iter_next(it)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn LLVMPY_DisposeGlobalsIter(it: LLVMGlobalsIteratorRef) {
+    state::record("LLVMPY_DisposeGlobalsIter");
+    dispose_iter(it);
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn LLVMPY_ModuleTypesIter(m: LLVMModuleRef) -> LLVMTypesIteratorRef {
+    state::record("LLVMPY_ModuleTypesIter");
+    new_iter(&module(m).contents.struct_types)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn LLVMPY_TypesIterNext(it: LLVMTypesIteratorRef) -> LLVMTypeRef {
+    state::record("LLVMPY_TypesIterNext");
+    iter_next(it)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn LLVMPY_DisposeTypesIter(it: LLVMTypesIteratorRef) {
+    state::record("LLVMPY_DisposeTypesIter");
+    dispose_iter(it);
+}
+
+macro_rules! child_iter {
+    ($iter:ident, $next:ident, $dispose:ident, $ref:ty, $field:ident) => {
+        #[no_mangle]
+        pub unsafe extern "C" fn $iter(v: LLVMValueRef) -> $ref {
+            state::record(stringify!($iter));
+            new_iter(&value(v).$field)
+        }
+
+        #[no_mangle]
+        pub unsafe extern "C" fn $next(it: $ref) -> LLVMValueRef {
+            state::record(stringify!($next));
+            iter_next(it)
+        }
+
+        #[no_mangle]
+        pub unsafe extern "C" fn $dispose(it: $ref) {
+            state::record(stringify!($dispose));
+            dispose_iter(it);
+        }
+    };
+}
+
+child_iter!(
+    LLVMPY_FunctionBlocksIter,
+    LLVMPY_BlocksIterNext,
+    LLVMPY_DisposeBlocksIter,
+    LLVMBlocksIteratorRef,
+    blocks
+);
+child_iter!(
+    LLVMPY_FunctionArgumentsIter,
+    LLVMPY_ArgumentsIterNext,
+    LLVMPY_DisposeArgumentsIter,
+    LLVMArgumentsIteratorRef,
+    arguments
+);
+child_iter!(
+    LLVMPY_BlockInstructionsIter,
+    LLVMPY_InstructionsIterNext,
+    LLVMPY_DisposeInstructionsIter,
+    LLVMInstructionsIteratorRef,
+    instructions
+);
+child_iter!(
+    LLVMPY_InstructionOperandsIter,
+    LLVMPY_OperandsIterNext,
+    LLVMPY_DisposeOperandsIter,
+    LLVMOperandsIteratorRef,
+    operands
+);
+
+#[no_mangle]
+pub unsafe extern "C" fn LLVMPY_FunctionAttributesIter(v: LLVMValueRef) -> LLVMAttributeListIteratorRef {
+    state::record("LLVMPY_FunctionAttributesIter");
+    new_iter(&value(v).attribute_sets)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn LLVMPY_CallInstAttributesIter(v: LLVMValueRef) -> LLVMAttributeListIteratorRef {
+    state::record("LLVMPY_CallInstAttributesIter");
+    new_iter(&value(v).attribute_sets)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn LLVMPY_InvokeInstAttributesIter(v: LLVMValueRef) -> LLVMAttributeListIteratorRef {
+    state::record("LLVMPY_InvokeInstAttributesIter");
+    new_iter(&value(v).attribute_sets)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn LLVMPY_AttributeListIterNext(it: LLVMAttributeListIteratorRef) -> *const c_char {
+    state::record("LLVMPY_AttributeListIterNext");
+    string_next(it)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn LLVMPY_DisposeAttributeListIter(it: LLVMAttributeListIteratorRef) {
+    state::record("LLVMPY_DisposeAttributeListIter");
+    dispose_iter(it);
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn LLVMPY_ArgumentAttributesIter(v: LLVMValueRef) -> LLVMAttributeSetIteratorRef {
+    state::record("LLVMPY_ArgumentAttributesIter");
+    new_iter(&value(v).attributes)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn LLVMPY_GlobalAttributesIter(v: LLVMValueRef) -> LLVMAttributeSetIteratorRef {
+    state::record("LLVMPY_GlobalAttributesIter");
+    new_iter(&value(v).attributes)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn LLVMPY_AttributeSetIterNext(it: LLVMAttributeSetIteratorRef) -> *const c_char {
+    state::record("LLVMPY_AttributeSetIterNext");
+    string_next(it)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn LLVMPY_DisposeAttributeSetIter(it: LLVMAttributeSetIteratorRef) {
+    state::record("LLVMPY_DisposeAttributeSetIter");
+    dispose_iter(it);
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn LLVMPY_GetValueName(v: LLVMValueRef) -> *const c_char {
+    state::record("LLVMPY_GetValueName");
+    value(v).name.as_ptr()
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn LLVMPY_GetValueKind(v: LLVMValueRef) -> i32 {
+    state::record("LLVMPY_GetValueKind");
+    value(v).kind as i32
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn LLVMPY_IsDeclaration(v: LLVMValueRef) -> i32 {
+    state::record("LLVMPY_IsDeclaration");
+    value(v).declaration as i32
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn LLVMPY_GetOpcodeName(v: LLVMValueRef) -> *const c_char {
+    state::record("LLVMPY_GetOpcodeName");
+    state::alloc_string(value(v).opcode.as_bytes())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn LLVMPY_GetTypeName(t: LLVMTypeRef) -> *const c_char {
+    state::record("LLVMPY_GetTypeName");
+    state::alloc_string(ty(t).name.as_bytes())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn LLVMPY_GetNamedFunction(
+    m: LLVMModuleRef,
+    name: *const c_char,
+) -> LLVMValueRef {
+    state::record("LLVMPY_GetNamedFunction");
+    find(&module(m).contents.functions, name, |f| &f.name)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn LLVMPY_GetNamedGlobalVariable(
+    m: LLVMModuleRef,
+    name: *const c_char,
+) -> LLVMValueRef {
+    state::record("LLVMPY_GetNamedGlobalVariable");
+    find(&module(m).contents.globals, name, |g| &g.name)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn LLVMPY_GetNamedStructType(
+    m: LLVMModuleRef,
+    name: *const c_char,
+) -> LLVMTypeRef {
+    state::record("LLVMPY_GetNamedStructType");
+    find(&module(m).contents.struct_types, name, |t| &t.name)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn LLVMPY_GetEnumAttributeKindForName(name: *const c_char, len: usize) -> u32 {
+    state::record("LLVMPY_GetEnumAttributeKindForName");
+    let name = std::slice::from_raw_parts(name as *const u8, len);
+    ENUM_ATTRIBUTES
+        .iter()
+        .position(|attribute| attribute.as_bytes() == name)
+        .map_or(0, |index| index as u32 + 1)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn LLVMPY_AddFunctionAttr(f: LLVMValueRef, kind: u32) {
+    state::record("LLVMPY_AddFunctionAttr");
+    let function = &mut *(f as *mut StubValue);
+    let name = ENUM_ATTRIBUTES[kind as usize - 1];
+    let attribute = CString::new(name).unwrap_or_default();
+    if !function.attributes.contains(&attribute) {
+        function.attributes.push(attribute);
+        function.attribute_sets = ir::function_attribute_sets(function);
+    }
+}
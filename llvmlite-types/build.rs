@@ -0,0 +1,24 @@
+use std::env;
+
+/// The directory containing the llvmlite library to link against.
+const LIB_DIR_VAR: &str = "LLVMLITE_LIB_DIR";
+/// How to link the library, either `dylib` (the default) or `static`.
+const LINK_KIND_VAR: &str = "LLVMLITE_LINK_KIND";
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed={LIB_DIR_VAR}");
+    println!("cargo:rerun-if-env-changed={LINK_KIND_VAR}");
+
+    // Without the `static` feature the library is loaded at runtime instead.
+    if env::var_os("CARGO_FEATURE_STATIC").is_none() {
+        return;
+    }
+
+    if let Some(dir) = env::var_os(LIB_DIR_VAR) {
+        println!("cargo:rustc-link-search=native={}", dir.to_string_lossy());
+    }
+
+    let kind = env::var(LINK_KIND_VAR).unwrap_or_else(|_| "dylib".to_string());
+    println!("cargo:rustc-link-lib={kind}=llvmlite");
+}
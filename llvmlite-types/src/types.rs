@@ -196,7 +196,7 @@ pub type LLVMJITEventListenerRef = *mut LLVMOpaqueJITEventListener;
 pub type LLVMAttributeRef = *mut LLVMOpaqueAttributeRef;
 
 #[repr(C)]
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum LLVMOpcode {
     LLVMRet = 1,
     LLVMBr = 2,
@@ -267,6 +267,86 @@ pub enum LLVMOpcode {
     LLVMCatchSwitch = 65,
 }
 
+impl LLVMOpcode {
+    /// Converts an opcode name as returned by `LLVMPY_GetOpcodeName`.
+    ///
+    /// Returns `None` for opcodes added by LLVM versions newer than these
+    /// definitions. The user opcodes have no name and are never returned.
+    pub fn from_name(name: &str) -> Option<Self> {
+        use LLVMOpcode::*;
+
+        let opcode = match name {
+            "ret" => LLVMRet,
+            "br" => LLVMBr,
+            "switch" => LLVMSwitch,
+            "indirectbr" => LLVMIndirectBr,
+            "invoke" => LLVMInvoke,
+            "unreachable" => LLVMUnreachable,
+            "callbr" => LLVMCallBr,
+            "fneg" => LLVMFNeg,
+            "add" => LLVMAdd,
+            "fadd" => LLVMFAdd,
+            "sub" => LLVMSub,
+            "fsub" => LLVMFSub,
+            "mul" => LLVMMul,
+            "fmul" => LLVMFMul,
+            "udiv" => LLVMUDiv,
+            "sdiv" => LLVMSDiv,
+            "fdiv" => LLVMFDiv,
+            "urem" => LLVMURem,
+            "srem" => LLVMSRem,
+            "frem" => LLVMFRem,
+            "shl" => LLVMShl,
+            "lshr" => LLVMLShr,
+            "ashr" => LLVMAShr,
+            "and" => LLVMAnd,
+            "or" => LLVMOr,
+            "xor" => LLVMXor,
+            "alloca" => LLVMAlloca,
+            "load" => LLVMLoad,
+            "store" => LLVMStore,
+            "getelementptr" => LLVMGetElementPtr,
+            "trunc" => LLVMTrunc,
+            "zext" => LLVMZExt,
+            "sext" => LLVMSExt,
+            "fptoui" => LLVMFPToUI,
+            "fptosi" => LLVMFPToSI,
+            "uitofp" => LLVMUIToFP,
+            "sitofp" => LLVMSIToFP,
+            "fptrunc" => LLVMFPTrunc,
+            "fpext" => LLVMFPExt,
+            "ptrtoint" => LLVMPtrToInt,
+            "inttoptr" => LLVMIntToPtr,
+            "bitcast" => LLVMBitCast,
+            "addrspacecast" => LLVMAddrSpaceCast,
+            "icmp" => LLVMICmp,
+            "fcmp" => LLVMFCmp,
+            "phi" => LLVMPHI,
+            "call" => LLVMCall,
+            "select" => LLVMSelect,
+            "va_arg" => LLVMVAArg,
+            "extractelement" => LLVMExtractElement,
+            "insertelement" => LLVMInsertElement,
+            "shufflevector" => LLVMShuffleVector,
+            "extractvalue" => LLVMExtractValue,
+            "insertvalue" => LLVMInsertValue,
+            "freeze" => LLVMFreeze,
+            "fence" => LLVMFence,
+            "cmpxchg" => LLVMAtomicCmpXchg,
+            "atomicrmw" => LLVMAtomicRMW,
+            "resume" => LLVMResume,
+            "landingpad" => LLVMLandingPad,
+            "cleanupret" => LLVMCleanupRet,
+            "catchret" => LLVMCatchRet,
+            "catchpad" => LLVMCatchPad,
+            "cleanuppad" => LLVMCleanupPad,
+            "catchswitch" => LLVMCatchSwitch,
+            _ => return None,
+        };
+        Some(opcode)
+    }
+}
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum LLVMTypeKind {
@@ -422,6 +502,45 @@ pub enum LLVMValueKind {
     LLVMPoisonValueKind,
 }
 
+impl LLVMValueKind {
+    /// Converts the raw kind returned by `LLVMPY_GetValueKind`.
+    ///
+    /// Returns `None` for kinds added by LLVM versions newer than these definitions.
+    pub fn from_raw(raw: i32) -> Option<Self> {
+        use LLVMValueKind::*;
+
+        const KINDS: [LLVMValueKind; 26] = [
+            LLVMArgumentValueKind,
+            LLVMBasicBlockValueKind,
+            LLVMMemoryUseValueKind,
+            LLVMMemoryDefValueKind,
+            LLVMMemoryPhiValueKind,
+            LLVMFunctionValueKind,
+            LLVMGlobalAliasValueKind,
+            LLVMGlobalIFuncValueKind,
+            LLVMGlobalVariableValueKind,
+            LLVMBlockAddressValueKind,
+            LLVMConstantExprValueKind,
+            LLVMConstantArrayValueKind,
+            LLVMConstantStructValueKind,
+            LLVMConstantVectorValueKind,
+            LLVMUndefValueValueKind,
+            LLVMConstantAggregateZeroValueKind,
+            LLVMConstantDataArrayValueKind,
+            LLVMConstantDataVectorValueKind,
+            LLVMConstantIntValueKind,
+            LLVMConstantFPValueKind,
+            LLVMConstantPointerNullValueKind,
+            LLVMConstantTokenNoneValueKind,
+            LLVMMetadataAsValueValueKind,
+            LLVMInlineAsmValueKind,
+            LLVMInstructionValueKind,
+            LLVMPoisonValueKind,
+        ];
+        usize::try_from(raw).ok().and_then(|index| KINDS.get(index).copied())
+    }
+}
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum LLVMIntPredicate {
@@ -0,0 +1,516 @@
+use std::borrow::Borrow;
+use std::convert::Infallible;
+use std::error::Error;
+use std::ffi::c_char;
+use std::fmt::{Debug, Display, Formatter};
+use std::str::FromStr;
+
+use crate::binding::LLVMMethods;
+use crate::module::take_string;
+
+/// Attributes which carry a value, so cannot be added by their kind alone.
+const VALUE_ATTRIBUTES: [&str; 16] = [
+    "align",
+    "alignstack",
+    "allockind",
+    "allocsize",
+    "byref",
+    "byval",
+    "dereferenceable",
+    "dereferenceable_or_null",
+    "elementtype",
+    "inalloca",
+    "memory",
+    "nofpclass",
+    "preallocated",
+    "sret",
+    "uwtable",
+    "vscale_range",
+];
+
+/// An error produced when an attribute cannot be added.
+pub enum AttributeError {
+    /// LLVM has no enum attribute with this name.
+    UnknownAttribute {
+        name: String,
+    },
+    /// The attribute carries a value, such as `align 8`, so it is not an enum
+    /// attribute and cannot be added by name.
+    RequiresValue {
+        attribute: Attribute,
+    },
+    /// String attributes, such as `"frame-pointer"="all"`, have no kind and
+    /// cannot be added by kind.
+    StringAttribute {
+        key: String,
+    },
+}
+
+impl Display for AttributeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AttributeError::UnknownAttribute { name } => {
+                write!(f, "AttributeError(unknown_attribute={name:?})")
+            },
+            AttributeError::RequiresValue { attribute } => {
+                write!(f, "AttributeError(requires_value=\"{attribute}\")")
+            },
+            AttributeError::StringAttribute { key } => {
+                write!(f, "AttributeError(string_attribute={key:?})")
+            },
+        }
+    }
+}
+
+impl Debug for AttributeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self}")
+    }
+}
+
+impl Error for AttributeError {}
+
+/// Declares [Attribute] with a unit variant for each enum attribute.
+macro_rules! attributes {
+    ($($variant:ident = $name:literal,)*) => {
+        /// An attribute of a function, parameter, call site or global.
+        ///
+        /// Enum attributes that are simply present or absent, such as
+        /// `nounwind`, are unit variants. Attributes this crate does not
+        /// understand, such as ones added by a newer LLVM, are kept verbatim
+        /// as [Attribute::Other].
+        #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+        pub enum Attribute {
+            $(
+                #[doc = concat!("`", $name, "`")]
+                $variant,
+            )*
+            /// `align N`, the alignment of a pointer parameter or return value.
+            Align(u64),
+            /// `alignstack(N)`, the stack alignment of a function.
+            AlignStack(u64),
+            /// `dereferenceable(N)`, the number of bytes known to be readable.
+            Dereferenceable(u64),
+            /// `dereferenceable_or_null(N)`, as [Attribute::Dereferenceable]
+            /// unless the pointer is null.
+            DereferenceableOrNull(u64),
+            /// `byval(<ty>)`, holding the type as written in IR.
+            ByVal(String),
+            /// `byref(<ty>)`, holding the type as written in IR.
+            ByRef(String),
+            /// `sret(<ty>)`, holding the type as written in IR.
+            StructRet(String),
+            /// `preallocated(<ty>)`, holding the type as written in IR.
+            Preallocated(String),
+            /// `inalloca(<ty>)`, holding the type as written in IR.
+            InAlloca(String),
+            /// `elementtype(<ty>)`, holding the type as written in IR.
+            ElementType(String),
+            /// A `"key"="value"` string attribute, the value is empty if the
+            /// attribute is only a key.
+            String { key: String, value: String },
+            /// Any other attribute, as LLVM printed it.
+            Other(String),
+        }
+
+        impl Attribute {
+            /// Returns the IR name of an enum attribute, e.g. `nounwind`.
+            ///
+            /// Attributes carrying a value have no enum name.
+            pub fn enum_name(&self) -> Option<&'static str> {
+                match self {
+                    $(Attribute::$variant => Some($name),)*
+                    _ => None,
+                }
+            }
+
+            fn from_enum_name(name: &str) -> Option<Self> {
+                match name {
+                    $($name => Some(Attribute::$variant),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+attributes! {
+    AllocAlign = "allocalign",
+    AllocatedPointer = "allocptr",
+    AlwaysInline = "alwaysinline",
+    ArgMemOnly = "argmemonly",
+    Builtin = "builtin",
+    Cold = "cold",
+    Convergent = "convergent",
+    DisableSanitizerInstrumentation = "disable_sanitizer_instrumentation",
+    FnRetThunkExtern = "fn_ret_thunk_extern",
+    Hot = "hot",
+    ImmArg = "immarg",
+    InReg = "inreg",
+    InaccessibleMemOnly = "inaccessiblememonly",
+    InaccessibleMemOrArgMemOnly = "inaccessiblemem_or_argmemonly",
+    InlineHint = "inlinehint",
+    JumpTable = "jumptable",
+    MinSize = "minsize",
+    MustProgress = "mustprogress",
+    Naked = "naked",
+    Nest = "nest",
+    NoAlias = "noalias",
+    NoBuiltin = "nobuiltin",
+    NoCallback = "nocallback",
+    NoCapture = "nocapture",
+    NoCfCheck = "nocf_check",
+    NoDuplicate = "noduplicate",
+    NoFree = "nofree",
+    NoImplicitFloat = "noimplicitfloat",
+    NoInline = "noinline",
+    NoMerge = "nomerge",
+    NoProfile = "noprofile",
+    NoRecurse = "norecurse",
+    NoRedZone = "noredzone",
+    NoReturn = "noreturn",
+    NoSanitizeBounds = "nosanitize_bounds",
+    NoSanitizeCoverage = "nosanitize_coverage",
+    NoSync = "nosync",
+    NoUndef = "noundef",
+    NoUnwind = "nounwind",
+    NonLazyBind = "nonlazybind",
+    NonNull = "nonnull",
+    NullPointerIsValid = "null_pointer_is_valid",
+    OptForFuzzing = "optforfuzzing",
+    OptimizeForSize = "optsize",
+    OptimizeNone = "optnone",
+    PresplitCoroutine = "presplitcoroutine",
+    ReadNone = "readnone",
+    ReadOnly = "readonly",
+    Returned = "returned",
+    ReturnsTwice = "returns_twice",
+    SExt = "signext",
+    SafeStack = "safestack",
+    SanitizeAddress = "sanitize_address",
+    SanitizeHWAddress = "sanitize_hwaddress",
+    SanitizeMemTag = "sanitize_memtag",
+    SanitizeMemory = "sanitize_memory",
+    SanitizeThread = "sanitize_thread",
+    ShadowCallStack = "shadowcallstack",
+    Speculatable = "speculatable",
+    SpeculativeLoadHardening = "speculative_load_hardening",
+    StackProtect = "ssp",
+    StackProtectReq = "sspreq",
+    StackProtectStrong = "sspstrong",
+    StrictFP = "strictfp",
+    SwiftAsync = "swiftasync",
+    SwiftError = "swifterror",
+    SwiftSelf = "swiftself",
+    UWTable = "uwtable",
+    WillReturn = "willreturn",
+    WriteOnly = "writeonly",
+    ZExt = "zeroext",
+}
+
+impl Attribute {
+    /// Parses one attribute in the form LLVM prints it, e.g. `nounwind`,
+    /// `align 16`, `dereferenceable(8)` or `"frame-pointer"="all"`.
+    fn from_llvm(attribute: &str) -> Self {
+        let attribute = attribute.trim();
+        if attribute.starts_with('"') {
+            return Self::parse_string(attribute)
+                .unwrap_or_else(|| Attribute::Other(attribute.to_owned()));
+        }
+
+        let (name, argument) = match attribute.split_once([' ', '(']) {
+            Some((name, argument)) => (name, Some(argument.strip_suffix(')').unwrap_or(argument))),
+            None => (attribute, None),
+        };
+        let int = || argument.and_then(|argument| argument.trim().parse().ok());
+        let ty = || argument.map(|argument| argument.trim().to_owned());
+
+        let parsed = match name {
+            "align" => int().map(Attribute::Align),
+            "alignstack" => int().map(Attribute::AlignStack),
+            "dereferenceable" => int().map(Attribute::Dereferenceable),
+            "dereferenceable_or_null" => int().map(Attribute::DereferenceableOrNull),
+            "byval" => ty().map(Attribute::ByVal),
+            "byref" => ty().map(Attribute::ByRef),
+            "sret" => ty().map(Attribute::StructRet),
+            "preallocated" => ty().map(Attribute::Preallocated),
+            "inalloca" => ty().map(Attribute::InAlloca),
+            "elementtype" => ty().map(Attribute::ElementType),
+            _ if argument.is_none() => Self::from_enum_name(name),
+            _ => None,
+        };
+        parsed.unwrap_or_else(|| Attribute::Other(attribute.to_owned()))
+    }
+
+    /// Returns the name to look the attribute's kind up by, if it is an enum
+    /// attribute.
+    ///
+    /// Bare names this crate does not know are assumed to be enum attributes
+    /// from a newer LLVM, which LLVM itself then accepts or rejects. Names
+    /// that carry a value in some LLVM version, such as `uwtable` which is an
+    /// int attribute from LLVM 15, are rejected even as unit variants.
+    pub(crate) fn kind_name(&self) -> Result<&str, AttributeError> {
+        let name = match self {
+            Attribute::Other(name)
+                if name.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_') =>
+            {
+                name
+            },
+            Attribute::String { key, .. } => {
+                return Err(AttributeError::StringAttribute { key: key.clone() });
+            },
+            _ => match self.enum_name() {
+                Some(name) => name,
+                None => return Err(AttributeError::RequiresValue { attribute: self.clone() }),
+            },
+        };
+
+        if VALUE_ATTRIBUTES.contains(&name) {
+            return Err(AttributeError::RequiresValue { attribute: self.clone() });
+        }
+        Ok(name)
+    }
+
+    /// Parses `"key"` or `"key"="value"`, where the value may contain `\XX`
+    /// hex escapes.
+    fn parse_string(attribute: &str) -> Option<Self> {
+        let (key, rest) = attribute[1..].split_once('"')?;
+        let value = match rest.strip_prefix('=') {
+            Some(value) => unescape(value.strip_prefix('"')?.strip_suffix('"')?)?,
+            None if rest.is_empty() => String::new(),
+            None => return None,
+        };
+        Some(Attribute::String { key: key.to_owned(), value })
+    }
+}
+
+impl FromStr for Attribute {
+    type Err = Infallible;
+
+    /// Parses an attribute the way LLVM prints it, anything unrecognised
+    /// becomes [Attribute::Other].
+    fn from_str(attribute: &str) -> Result<Self, Self::Err> {
+        Ok(Attribute::from_llvm(attribute))
+    }
+}
+
+impl From<&str> for Attribute {
+    fn from(attribute: &str) -> Self {
+        Attribute::from_llvm(attribute)
+    }
+}
+
+impl Display for Attribute {
+    /// Writes the attribute the way LLVM prints it.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(name) = self.enum_name() {
+            return f.write_str(name);
+        }
+
+        match self {
+            Attribute::Align(align) => write!(f, "align {align}"),
+            Attribute::AlignStack(align) => write!(f, "alignstack({align})"),
+            Attribute::Dereferenceable(bytes) => write!(f, "dereferenceable({bytes})"),
+            Attribute::DereferenceableOrNull(bytes) => write!(f, "dereferenceable_or_null({bytes})"),
+            Attribute::ByVal(ty) => write!(f, "byval({ty})"),
+            Attribute::ByRef(ty) => write!(f, "byref({ty})"),
+            Attribute::StructRet(ty) => write!(f, "sret({ty})"),
+            Attribute::Preallocated(ty) => write!(f, "preallocated({ty})"),
+            Attribute::InAlloca(ty) => write!(f, "inalloca({ty})"),
+            Attribute::ElementType(ty) => write!(f, "elementtype({ty})"),
+            Attribute::String { key, value } if value.is_empty() => write!(f, "\"{key}\""),
+            Attribute::String { key, value } => {
+                write!(f, "\"{key}\"=\"")?;
+                for byte in value.bytes() {
+                    if (b' '..=b'~').contains(&byte) && byte != b'\\' && byte != b'"' {
+                        write!(f, "{}", byte as char)?;
+                    } else {
+                        write!(f, "\\{byte:02X}")?;
+                    }
+                }
+                f.write_str("\"")
+            },
+            Attribute::Other(attribute) => f.write_str(attribute),
+            _ => unreachable!("enum attributes are written by name"),
+        }
+    }
+}
+
+/// The attributes of a function, parameter, call site or global.
+///
+/// Attributes are kept in the order LLVM reports them, without duplicates.
+#[derive(Clone, Debug, Default)]
+pub struct AttributeSet {
+    attributes: Vec<Attribute>,
+}
+
+impl AttributeSet {
+    /// Returns if the set contains the attribute.
+    pub fn contains(&self, attribute: impl Borrow<Attribute>) -> bool {
+        self.attributes.contains(attribute.borrow())
+    }
+
+    /// Returns the value of the string attribute with the given key.
+    pub fn string_value(&self, key: &str) -> Option<&str> {
+        self.attributes.iter().find_map(|attribute| match attribute {
+            Attribute::String { key: k, value } if k == key => Some(value.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Returns an iterator over the attributes in the set.
+    pub fn iter(&self) -> std::slice::Iter<'_, Attribute> {
+        self.attributes.iter()
+    }
+
+    /// Returns the number of attributes in the set.
+    pub fn len(&self) -> usize {
+        self.attributes.len()
+    }
+
+    /// Returns if the set has no attributes.
+    pub fn is_empty(&self) -> bool {
+        self.attributes.is_empty()
+    }
+
+    /// Drains a native attribute iterator, which yields strings holding one
+    /// or more attributes until it returns null, and disposes it.
+    pub(crate) unsafe fn collect<I: Copy>(
+        lib: &LLVMMethods,
+        iter: I,
+        next: unsafe extern "C" fn(I) -> *const c_char,
+        dispose: unsafe extern "C" fn(I),
+    ) -> Self {
+        Self::collect_where(lib, iter, next, dispose, |_| true)
+    }
+
+    /// Like [AttributeSet::collect], but only keeps the attributes of the
+    /// `index`th string.
+    ///
+    /// An attribute list iterator yields one string per set: the function's,
+    /// the return value's, then each parameter's.
+    pub(crate) unsafe fn collect_nth<I: Copy>(
+        lib: &LLVMMethods,
+        iter: I,
+        next: unsafe extern "C" fn(I) -> *const c_char,
+        dispose: unsafe extern "C" fn(I),
+        index: usize,
+    ) -> Self {
+        Self::collect_where(lib, iter, next, dispose, |i| i == index)
+    }
+
+    unsafe fn collect_where<I: Copy>(
+        lib: &LLVMMethods,
+        iter: I,
+        next: unsafe extern "C" fn(I) -> *const c_char,
+        dispose: unsafe extern "C" fn(I),
+        keep: impl Fn(usize) -> bool,
+    ) -> Self {
+        let mut set = AttributeSet::default();
+        for i in 0.. {
+            let ptr = next(iter);
+            if ptr.is_null() {
+                break;
+            }
+            // Every string is taken, kept or not, so that it is disposed.
+            let attributes = take_string(lib, ptr);
+            if keep(i) {
+                set.extend(split(&attributes).into_iter().map(Attribute::from_llvm));
+            }
+        }
+        dispose(iter);
+        set
+    }
+}
+
+impl FromStr for AttributeSet {
+    type Err = Infallible;
+
+    /// Parses a space separated list of attributes, as printed by LLVM.
+    fn from_str(attributes: &str) -> Result<Self, Self::Err> {
+        Ok(split(attributes).into_iter().map(Attribute::from_llvm).collect())
+    }
+}
+
+impl Extend<Attribute> for AttributeSet {
+    fn extend<T: IntoIterator<Item = Attribute>>(&mut self, iter: T) {
+        for attribute in iter {
+            if !self.attributes.contains(&attribute) {
+                self.attributes.push(attribute);
+            }
+        }
+    }
+}
+
+impl FromIterator<Attribute> for AttributeSet {
+    fn from_iter<T: IntoIterator<Item = Attribute>>(iter: T) -> Self {
+        let mut set = AttributeSet::default();
+        set.extend(iter);
+        set
+    }
+}
+
+impl IntoIterator for AttributeSet {
+    type Item = Attribute;
+    type IntoIter = std::vec::IntoIter<Attribute>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.attributes.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a AttributeSet {
+    type Item = &'a Attribute;
+    type IntoIter = std::slice::Iter<'a, Attribute>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.attributes.iter()
+    }
+}
+
+/// Splits a list of attributes at the spaces outside of parentheses and
+/// quotes, keeping `align N` together.
+fn split(attributes: &str) -> Vec<&str> {
+    let mut pieces = Vec::new();
+    let (mut depth, mut quoted, mut start) = (0, false, 0);
+
+    for (i, c) in attributes.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '(' if !quoted => depth += 1,
+            ')' if !quoted => depth -= 1,
+            ' ' if depth == 0 && !quoted => {
+                let piece = &attributes[start..i];
+                if piece != "align" {
+                    pieces.push(piece);
+                    start = i + 1;
+                }
+            },
+            _ => {},
+        }
+    }
+    pieces.push(&attributes[start..]);
+
+    pieces.retain(|piece| !piece.is_empty());
+    pieces
+}
+
+/// Undoes LLVM's `printEscapedString`, which writes `\XX` for each byte that
+/// is not printable, a quote or a backslash.
+fn unescape(value: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut rest = value.as_bytes();
+
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'\\' {
+            let hex = std::str::from_utf8(tail.get(..2)?).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+
+    String::from_utf8(bytes).ok()
+}
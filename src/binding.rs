@@ -2,27 +2,64 @@ use std::error::Error;
 use std::ffi::{c_char, CStr, CString, OsStr};
 use std::fmt::{Debug, Display, Formatter};
 use std::mem;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
-use libloading::{Library, Symbol};
+use libloading::Library;
 
 use llvmlite_types::*;
-use crate::wrappers::{ByteString, Context, Utf8String};
+use crate::discover;
+use crate::version::{LlvmVersion, MAX_LLVM_MAJOR, MIN_LLVM_MAJOR};
+use crate::wrappers::{ByteString, Context, LlvmBytes, StringError, Utf8String};
 
 pub enum LoadError {
-    LoadFunction {
-        inner: libloading::Error,
-        target: String,
+    /// The library is missing symbols every supported llvmlite provides.
+    MissingSymbols {
+        /// Every required symbol which could not be found.
+        missing: Vec<&'static str>,
+        /// The path of the file the library was loaded from, if known.
+        ///
+        /// On unix this is resolved from any symbol the library does provide,
+        /// so it is known even when the loader searched for the library. If it
+        /// cannot be resolved, or on other platforms, it is the path as given.
+        path: Option<PathBuf>,
+        /// The LLVM version reported by the library, if it could be read.
+        version: Option<LlvmVersion>,
     },
+    /// The library could not be opened.
     LoadLLVM {
+        /// The error reported by the platform's loader.
         inner: libloading::Error,
-    }
+    },
+    /// [LLVMLite::discover] found no library.
+    NotFound {
+        /// Every path which was searched, in order.
+        tried: Vec<PathBuf>,
+    },
+    /// The library was built against a version of LLVM this crate does not support.
+    IncompatibleVersion {
+        /// The LLVM version reported by the library.
+        found: LlvmVersion,
+    },
 }
 
 impl Display for LoadError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
-            LoadError::LoadFunction { target, inner } => write!(f, "LoadError(function={target:?}, error={inner}, help=\"Are you using a llvmlite.dll from 0.40.0+?\")"),
+            LoadError::MissingSymbols { missing, path, version } => {
+                write!(f, "LoadError(missing_symbols={missing:?}")?;
+                if let Some(path) = path {
+                    write!(f, ", path={path:?}")?;
+                }
+                if let Some(version) = version {
+                    write!(f, ", version={version}")?;
+                }
+                write!(f, ", help=\"Are you using a llvmlite.dll from 0.40.0+?\")")
+            },
             LoadError::LoadLLVM { inner } => write!(f, "LoadError(load_llvm={inner})"),
+            LoadError::IncompatibleVersion { found } => write!(f, "LoadError(incompatible_version={found}, supported=\"LLVM {MIN_LLVM_MAJOR}.x to {MAX_LLVM_MAJOR}.x\")"),
+            LoadError::NotFound { tried } => write!(f, "LoadError(not_found, tried={tried:?}, help=\"Set {} to the path of the llvmlite library\")", discover::LIBRARY_ENV_VAR),
         }
     }
 }
@@ -35,12 +72,76 @@ impl Debug for LoadError {
 
 impl Error for LoadError {}
 
+/// A group of symbols which are not present in every build of llvmlite.
+///
+/// Libraries missing any of these symbols can still be loaded, the
+/// availability of each group can be checked with [LLVMLite::supports].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Capability {
+    /// The individual `LLVMPY_Add*Pass` functions.
+    Passes,
+    /// Intel's short vector math library support check.
+    Svml,
+    /// The reference count pruning pass used by numba.
+    RefPrune,
+    /// JIT event listener support for execution engines.
+    JitEvents,
+    /// The DOT graph printing passes and `LLVMPY_WriteCFG`.
+    DotPrinters,
+}
+
+/// An optional symbol was called which the loaded library does not provide.
+pub struct MissingSymbol {
+    pub name: &'static str,
+    pub capability: Capability,
+}
+
+impl Display for MissingSymbol {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "MissingSymbol(function={:?}, capability={:?})", self.name, self.capability)
+    }
+}
+
+impl Debug for MissingSymbol {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self}")
+    }
+}
+
+impl Error for MissingSymbol {}
+
+/// Whether a library provides one of the symbols this crate uses.
+///
+/// Produced by [LLVMLite::probe] and [LLVMLite::symbols] to help track down
+/// which symbols an incompatible library is missing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SymbolStatus {
+    /// The name of the symbol.
+    pub name: &'static str,
+    /// The capability the symbol belongs to, or `None` if it is required.
+    pub capability: Option<Capability>,
+    /// If the library provides the symbol.
+    pub provided: bool,
+}
+
+/// A loaded llvmlite library.
+///
+/// # Thread safety
+/// `LLVMLite` is `Send` and `Sync`, it can be shared between threads freely and
+/// each thread can create its own [Context] from it. LLVM contexts are not thread
+/// safe, so a [Context] is `Send` but not `Sync`, and anything borrowing a context,
+/// such as a [Module](crate::Module), is neither. IR can therefore only move
+/// between threads together with the context that owns it.
 pub struct LLVMLite {
-    // NOTE: This is not actually `'static` it's bound to the lifetime of `Self`
-    methods: LLVMMethods<'static>,
-    _library: Library,
+    methods: Arc<LLVMMethods>,
+    version: LlvmVersion,
 }
 
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<LLVMLite>();
+};
+
 impl LLVMLite {
     /// Attempts to load the LLVMLite library DLL/SO.
     ///
@@ -57,43 +158,236 @@ impl LLVMLite {
     /// These routines may be executed when the library is unloaded.
     pub unsafe fn new(path: impl AsRef<OsStr>) -> Result<Self, LoadError> {
         let path = libloading::library_filename(path.as_ref());
-        let library = Library::new(path)
+        Self::from_path(path)
+    }
+
+    /// Attempts to load the LLVMLite library DLL/SO from an exact path.
+    ///
+    /// Unlike [LLVMLite::new] the path is used as-is and no platform specific
+    /// prefix or extension is added.
+    ///
+    /// # Safety
+    /// The same requirements as [LLVMLite::new] apply.
+    pub unsafe fn from_path(path: impl AsRef<OsStr>) -> Result<Self, LoadError> {
+        let library = Library::new(path.as_ref())
+            .map_err(|e| LoadError::LoadLLVM { inner: e })?;
+        Self::load(library, Some(PathBuf::from(path.as_ref())))
+    }
+
+    /// Resolves the LLVMLite symbols from the current process image.
+    ///
+    /// This avoids loading a second copy of LLVM when the process already has
+    /// llvmlite loaded, for example inside a Python extension module after
+    /// `llvmlite.binding` has been imported.
+    ///
+    /// The global context is then shared with `llvmlite.binding`, which does not
+    /// know about the handle [LLVMLite::get_global_context] returns, so it must
+    /// not be used while Python code may be using LLVM.
+    ///
+    /// Only symbols which are globally visible can be found this way. Python's
+    /// `ctypes` loads libraries with `RTLD_LOCAL` by default on Linux, in which
+    /// case the library should be re-opened with `RTLD_NOLOAD` and passed to
+    /// [LLVMLite::from_library] instead.
+    ///
+    /// # Safety
+    /// The same requirements as [LLVMLite::from_library] apply.
+    pub unsafe fn from_current_process() -> Result<Self, LoadError> {
+        #[cfg(unix)]
+        let library = libloading::os::unix::Library::this();
+        #[cfg(windows)]
+        let library = libloading::os::windows::Library::this()
             .map_err(|e| LoadError::LoadLLVM { inner: e })?;
-        let methods = LLVMMethods::load(&library)?;
-        let methods = mem::transmute::<LLVMMethods<'_>, LLVMMethods<'static>>(methods);
+
+        Self::load(library.into(), None)
+    }
+
+    /// Attempts to find and load the LLVMLite library installed on the host.
+    ///
+    /// The following locations are searched in order:
+    ///
+    /// - The path in the `LLVMLITE_LIB` environment variable, which may be
+    ///   the library itself or the directory containing it.
+    /// - The `llvmlite/binding/` directory of any Python site-packages found via
+    ///   `PYTHONPATH`, the active virtualenv or conda environment, the user site
+    ///   and the system wide Python installs.
+    /// - The system loader search paths.
+    ///
+    /// The first library found is loaded, if it fails to load the error is
+    /// returned rather than continuing the search. If nothing is found
+    /// [LoadError::NotFound] is returned with every path that was tried.
+    ///
+    /// # Safety
+    /// The same requirements as [LLVMLite::new] apply.
+    pub unsafe fn discover() -> Result<Self, LoadError> {
+        let mut tried = Vec::new();
+
+        for candidate in discover::candidates() {
+            if candidate.is_file() {
+                return Self::from_path(candidate);
+            }
+            tried.push(candidate);
+        }
+
+        // Fall back to letting the OS loader search its own paths.
+        let filename = discover::library_filename();
+        match Library::new(&filename) {
+            // The OS searches for the library, the actual path is resolved on load.
+            Ok(library) => Self::load(library, Some(PathBuf::from(filename))),
+            Err(_) => {
+                tried.push(PathBuf::from(filename));
+                Err(LoadError::NotFound { tried })
+            },
+        }
+    }
+
+    /// Uses an already opened LLVMLite library.
+    ///
+    /// This is useful when the library has to be opened with specific flags,
+    /// e.g. `RTLD_NOLOAD` to attach to a copy that something else in the process,
+    /// like `llvmlite.binding`, has already loaded. The global context is then
+    /// shared with those users too, see [LLVMLite::get_global_context].
+    ///
+    /// # Safety
+    /// The library must be a llvmlite library, the same requirements as
+    /// [LLVMLite::new] apply to its termination routines.
+    pub unsafe fn from_library(library: Library) -> Result<Self, LoadError> {
+        Self::load(library, None)
+    }
+
+    unsafe fn load(library: Library, path: Option<PathBuf>) -> Result<Self, LoadError> {
+        // The version is checked before anything else so an unsupported library
+        // is reported as such rather than as whichever symbols happen to be missing.
+        let version = match load_fn(&library, VERSION_SYMBOL) {
+            Some(get_version_info) => Some(check_version(get_version_info)?),
+            None => None,
+        };
+
+        let path = resolved_path(&library).or(path);
+        let (methods, version) = match (LLVMMethods::load(library), version) {
+            (Ok(methods), Some(version)) => (methods, version),
+            // The version symbol is part of the table, so if it is missing the
+            // table fails to load and already lists it.
+            (result, version) => {
+                let missing = result.err().unwrap_or_default();
+                return Err(LoadError::MissingSymbols { missing, path, version });
+            },
+        };
+
+        Ok(Self {
+            methods: Arc::new(methods),
+            version,
+        })
+    }
+
+    /// Uses the llvmlite library linked into the binary at build time.
+    ///
+    /// This is only available with the `static` feature, which links `llvmlite`
+    /// from `LLVMLITE_LIB_DIR` or the linker's default search paths. The library
+    /// is linked as a `dylib` unless `LLVMLITE_LINK_KIND=static` is set, in which
+    /// case the LLVM libraries it depends on must be linked as well.
+    ///
+    /// Unlike a dynamically loaded library every symbol must be present when
+    /// linking, including those of the optional [Capability] groups.
+    #[cfg(feature = "static")]
+    pub fn linked() -> Result<Self, LoadError> {
+        let get_version_info = llvmlite_types::linked::LLVMPY_GetVersionInfo;
+        let version = unsafe { check_version(get_version_info)? };
 
         Ok(Self {
-            methods,
-            _library: library,
+            methods: Arc::new(LLVMMethods::linked()),
+            version,
         })
     }
 
+    /// Checks which of the symbols used by this crate an opened library provides.
+    ///
+    /// Unlike [LLVMLite::from_library] this never fails, so it can be used to list
+    /// everything a library is missing.
+    ///
+    /// # Safety
+    /// The library must not be unloaded while this is running.
+    pub unsafe fn probe(library: &Library) -> Vec<SymbolStatus> {
+        LLVMMethods::SYMBOLS
+            .iter()
+            .map(|&(name, capability)| SymbolStatus {
+                name,
+                capability,
+                provided: library.get::<*const ()>(name.as_bytes()).is_ok(),
+            })
+            .collect()
+    }
+
+    /// Lists the symbols used by this crate and whether the loaded library provides them.
+    ///
+    /// Every required symbol is always provided, otherwise the library would have
+    /// failed to load, so this mostly shows which optional symbols are missing.
+    pub fn symbols(&self) -> Vec<SymbolStatus> {
+        self.methods.symbols()
+    }
+
+    /// Returns the version of LLVM the loaded library was built against.
+    pub fn llvm_version(&self) -> LlvmVersion {
+        self.version
+    }
+
+    /// Returns if every symbol belonging to the given capability was loaded.
+    ///
+    /// Calling into a capability which is not supported returns a [MissingSymbol]
+    /// error rather than failing when the library is loaded.
+    pub fn supports(&self, capability: Capability) -> bool {
+        !self.methods.unavailable.contains(&capability)
+    }
+
+    /// Returns if LLVM was built with Intel's short vector math library, which
+    /// numba uses for vectorised math functions.
+    pub fn has_svml_support(&self) -> Result<bool, MissingSymbol> {
+        let has_svml_support = self.methods.has_svml_support.get()?;
+        Ok(unsafe { has_svml_support() } != 0)
+    }
+
     /// Creates a new string from a given input.
     ///
     /// This string is re-allocated by LLVM which can then be passed back
     /// and forth if needed.
+    ///
+    /// # Panics
+    /// If the string contains a NUL byte or LLVM produces a non-UTF-8 copy,
+    /// see [LLVMLite::try_create_string] for a non-panicking version.
     pub fn create_string(&self, s: impl Into<String>) -> Utf8String {
-        let c_str = CString::new(s.into())
-            .expect("CString should not contain null byte");
+        self.try_create_string(s)
+            .unwrap_or_else(|e| panic!("Failed to create string: {e}"))
+    }
+
+    /// Creates a new string from a given input.
+    ///
+    /// This string is re-allocated by LLVM which can then be passed back
+    /// and forth if needed.
+    pub fn try_create_string(
+        &self,
+        s: impl Into<String>,
+    ) -> Result<Utf8String, StringError> {
+        let c_str = CString::new(s.into())?;
         let ptr = c_str.as_ptr();
 
         // We re-alloc the string
         let ptr: *const c_char = unsafe { (self.methods.create_string)(ptr) };
 
         if ptr.is_null() {
-            return Utf8String::empty(self.methods.clone())
+            return Ok(Utf8String::empty(Arc::clone(&self.methods)))
         }
 
         let c_str = unsafe { CStr::from_ptr(ptr) };
-        let str_inner = c_str.to_str()
-            .expect("Re-alloc UTF-8 string has produced non-UTF-8 result");
+        let Ok(str_inner) = c_str.to_str() else {
+            unsafe { (self.methods.dispose_string)(ptr) };
+            return Err(StringError::InvalidUtf8);
+        };
         let inner = unsafe { mem::transmute::<&str, &'static str>(str_inner) };
 
-        Utf8String {
+        Ok(Utf8String {
             inner,
             ptr,
-            lib: self.methods.clone(),
-        }
+            lib: Arc::clone(&self.methods),
+        })
     }
 
     /// Creates a new non-UTF-8 encoded byte string.
@@ -101,7 +395,19 @@ impl LLVMLite {
     /// This string is re-allocated by LLVM which can then be passed back
     /// and forth if needed.
     pub fn create_byte_string(&self, s: impl Into<CString>) -> ByteString {
-        let c_str = s.into();
+        self.try_create_byte_string(s.into().into_bytes())
+            .unwrap_or_else(|e| panic!("Failed to create byte string: {e}"))
+    }
+
+    /// Creates a new non-UTF-8 encoded byte string.
+    ///
+    /// This string is re-allocated by LLVM which can then be passed back
+    /// and forth if needed.
+    pub fn try_create_byte_string(
+        &self,
+        s: impl Into<Vec<u8>>,
+    ) -> Result<ByteString, StringError> {
+        let c_str = CString::new(s)?;
         let len = c_str.as_bytes().len();
         let ptr = c_str.as_ptr();
 
@@ -109,26 +415,53 @@ impl LLVMLite {
         let ptr: *const c_char = unsafe { (self.methods.create_byte_string)(ptr, len) };
 
         if ptr.is_null() {
-            return ByteString::empty(self.methods.clone())
+            return Ok(ByteString::empty(Arc::clone(&self.methods)))
         }
 
         let inner = unsafe { CStr::from_ptr(ptr) };
 
-        ByteString {
+        Ok(ByteString {
             inner: Some(inner),
             ptr,
-            lib: self.methods.clone(),
-        }
+            lib: Arc::clone(&self.methods),
+        })
+    }
+
+    /// Copies a buffer of arbitrary bytes into a new LLVM allocation.
+    ///
+    /// Unlike [LLVMLite::create_byte_string] the bytes may contain NULs.
+    pub fn create_bytes(&self, bytes: &[u8]) -> LlvmBytes {
+        let ptr = bytes.as_ptr() as *const c_char;
+
+        // We re-alloc the buffer
+        let ptr = unsafe { (self.methods.create_byte_string)(ptr, bytes.len()) };
+
+        unsafe { LlvmBytes::from_raw(Arc::clone(&self.methods), ptr, bytes.len()) }
     }
 
     /// Gets the global LLVM context.
-    pub fn get_global_context(&self) -> Context {
+    ///
+    /// There is only one global context per process, shared by every [LLVMLite]
+    /// that loaded the same library, so only one handle to it can exist at a time.
+    /// This returns `None` while a previously returned handle is still alive,
+    /// otherwise two threads could use the same context at once. Earlier versions
+    /// returned a [Context] unconditionally.
+    ///
+    /// Only handles created by this crate are tracked. When sharing a library
+    /// with other users of llvmlite, such as Python's `llvmlite.binding` through
+    /// [LLVMLite::from_current_process], they may use the global context at the
+    /// same time, so the caller must ensure they do not.
+    pub fn get_global_context(&self) -> Option<Context> {
+        if GLOBAL_CONTEXT_TAKEN.swap(true, Ordering::AcqRel) {
+            return None;
+        }
+
         let ctx_ptr = unsafe { (self.methods.get_global_context)() };
-        Context {
+        Some(Context {
             is_global: true,
             inner: ctx_ptr,
-            lib: self.methods.clone(),
-        }
+            lib: Arc::clone(&self.methods),
+        })
     }
 
     /// Creates a new LLVM context.
@@ -137,483 +470,475 @@ impl LLVMLite {
         Context {
             is_global: false,
             inner: ctx_ptr,
-            lib: self.methods.clone(),
+            lib: Arc::clone(&self.methods),
         }
     }
 }
 
 
+/// Finds the file an opened library was loaded from, using the address of any
+/// symbol it provides.
+#[cfg(unix)]
+unsafe fn resolved_path(library: &Library) -> Option<PathBuf> {
+    use std::ffi::{c_int, c_void};
+    use std::os::unix::ffi::OsStrExt;
+
+    #[repr(C)]
+    struct DlInfo {
+        dli_fname: *const c_char,
+        dli_fbase: *mut c_void,
+        dli_sname: *const c_char,
+        dli_saddr: *mut c_void,
+    }
+
+    extern "C" {
+        fn dladdr(address: *const c_void, info: *mut DlInfo) -> c_int;
+    }
+
+    let address = LLVMMethods::SYMBOLS.iter().find_map(|(name, _)| {
+        library.get::<*const c_void>(name.as_bytes()).ok().map(|symbol| *symbol)
+    })?;
+
+    let mut info: DlInfo = mem::zeroed();
+    if dladdr(address, &mut info) == 0 || info.dli_fname.is_null() {
+        return None;
+    }
+    let path = CStr::from_ptr(info.dli_fname).to_bytes();
+    (!path.is_empty()).then(|| PathBuf::from(OsStr::from_bytes(path)))
+}
+
+#[cfg(not(unix))]
+unsafe fn resolved_path(_library: &Library) -> Option<PathBuf> {
+    None
+}
+
+/// Whether a handle to the global context is alive.
+///
+/// This is process-wide rather than per library: loading the same library twice
+/// gives two function tables but still only one LLVM global context.
+pub(crate) static GLOBAL_CONTEXT_TAKEN: AtomicBool = AtomicBool::new(false);
+
+/// A symbol which may not be present in the loaded library.
 #[derive(Clone)]
-pub struct LLVMMethods<'lib> {
-    pub(crate) set_cmd_line: Symbol<'lib, LLVMPY_SetCommandLine>,
-    pub(crate) parse_assembly: Symbol<'lib, LLVMPY_ParseAssembly>,
-    pub(crate) write_bitcode_to_string: Symbol<'lib, LLVMPY_WriteBitcodeToString>,
-    pub(crate) parse_bitcode: Symbol<'lib, LLVMPY_ParseBitcode>,
-    pub(crate) create_string: Symbol<'lib, LLVMPY_CreateString>,
-    pub(crate) create_byte_string: Symbol<'lib, LLVMPY_CreateByteString>,
-    pub(crate) dispose_string: Symbol<'lib, LLVMPY_DisposeString>,
-    pub(crate) get_global_context: Symbol<'lib, LLVMPY_GetGlobalContext>,
-    pub(crate) context_create: Symbol<'lib, LLVMPY_ContextCreate>,
-    pub(crate) context_dispose: Symbol<'lib, LLVMPY_ContextDispose>,
-    pub(crate) add_ref_prune_pass: Symbol<'lib, LLVMPY_AddRefPrunePass>,
-    pub(crate) dump_ref_prune_stats: Symbol<'lib, LLVMPY_DumpRefPruneStats>,
-    pub(crate) search_address_of_symbol: Symbol<'lib, LLVMPY_SearchAddressOfSymbol>,
-    pub(crate) add_symbol: Symbol<'lib, LLVMPY_AddSymbol>,
-    pub(crate) load_library_permanently: Symbol<'lib, LLVMPY_LoadLibraryPermanently>,
-    pub(crate) link_in_mc_jit: Symbol<'lib, LLVMPY_LinkInMCJIT>,
-    pub(crate) dispose_execution_engine: Symbol<'lib, LLVMPY_DisposeExecutionEngine>,
-    pub(crate) add_module: Symbol<'lib, LLVMPY_AddModule>,
-    pub(crate) remove_module: Symbol<'lib, LLVMPY_RemoveModule>,
-    pub(crate) finalize_object: Symbol<'lib, LLVMPY_FinalizeObject>,
-    pub(crate) create_mc_jit_compiler: Symbol<'lib, LLVMPY_CreateMCJITCompiler>,
-    pub(crate) get_global_value_address: Symbol<'lib, LLVMPY_GetGlobalValueAddress>,
-    pub(crate) get_function_address: Symbol<'lib, LLVMPY_GetFunctionAddress>,
-    pub(crate) run_static_constructors: Symbol<'lib, LLVMPY_RunStaticConstructors>,
-    pub(crate) run_static_destructors: Symbol<'lib, LLVMPY_RunStaticDestructors>,
-    pub(crate) add_global_mapping: Symbol<'lib, LLVMPY_AddGlobalMapping>,
-    pub(crate) get_execution_engine_target_data: Symbol<'lib, LLVMPY_GetExecutionEngineTargetData>,
-    pub(crate) try_allocate_executable_memory: Symbol<'lib, LLVMPY_TryAllocateExecutableMemory>,
-    pub(crate) enable_jit_events: Symbol<'lib, LLVMPY_EnableJITEvents>,
-    pub(crate) mc_jit_add_object_file: Symbol<'lib, LLVMPY_MCJITAddObjectFile>,
-    pub(crate) create_object_cache: Symbol<'lib, LLVMPY_CreateObjectCache>,
-    pub(crate) dispose_object_cache: Symbol<'lib, LLVMPY_DisposeObjectCache>,
-    pub(crate) set_object_cache: Symbol<'lib, LLVMPY_SetObjectCache>,
-    pub(crate) shutdown: Symbol<'lib, LLVMPY_Shutdown>,
-    pub(crate) get_version_info: Symbol<'lib, LLVMPY_GetVersionInfo>,
-    pub(crate) link_modules: Symbol<'lib, LLVMPY_LinkModules>,
-    pub(crate) dispose_module: Symbol<'lib, LLVMPY_DisposeModule>,
-    pub(crate) print_module_to_string: Symbol<'lib, LLVMPY_PrintModuleToString>,
-    pub(crate) get_module_source_file_name: Symbol<'lib, LLVMPY_GetModuleSourceFileName>,
-    pub(crate) get_module_name: Symbol<'lib, LLVMPY_GetModuleName>,
-    pub(crate) set_module_name: Symbol<'lib, LLVMPY_SetModuleName>,
-    pub(crate) get_named_function: Symbol<'lib, LLVMPY_GetNamedFunction>,
-    pub(crate) get_named_global_variable: Symbol<'lib, LLVMPY_GetNamedGlobalVariable>,
-    pub(crate) get_named_struct_type: Symbol<'lib, LLVMPY_GetNamedStructType>,
-    pub(crate) verify_module: Symbol<'lib, LLVMPY_VerifyModule>,
-    pub(crate) get_data_layout: Symbol<'lib, LLVMPY_GetDataLayout>,
-    pub(crate) set_data_layout: Symbol<'lib, LLVMPY_SetDataLayout>,
-    pub(crate) get_target: Symbol<'lib, LLVMPY_GetTarget>,
-    pub(crate) set_target: Symbol<'lib, LLVMPY_SetTarget>,
-    pub(crate) module_globals_iter: Symbol<'lib, LLVMPY_ModuleGlobalsIter>,
-    pub(crate) module_functions_iter: Symbol<'lib, LLVMPY_ModuleFunctionsIter>,
-    pub(crate) module_types_iter: Symbol<'lib, LLVMPY_ModuleTypesIter>,
-    pub(crate) globals_iter_next: Symbol<'lib, LLVMPY_GlobalsIterNext>,
-    pub(crate) functions_iter_next: Symbol<'lib, LLVMPY_FunctionsIterNext>,
-    pub(crate) types_iter_next: Symbol<'lib, LLVMPY_TypesIterNext>,
-    pub(crate) dispose_globals_iter: Symbol<'lib, LLVMPY_DisposeGlobalsIter>,
-    pub(crate) dispose_functions_iter: Symbol<'lib, LLVMPY_DisposeFunctionsIter>,
-    pub(crate) dispose_types_iter: Symbol<'lib, LLVMPY_DisposeTypesIter>,
-    pub(crate) clone_module: Symbol<'lib, LLVMPY_CloneModule>,
-    pub(crate) create_object_file: Symbol<'lib, LLVMPY_CreateObjectFile>,
-    pub(crate) dispose_object_file: Symbol<'lib, LLVMPY_DisposeObjectFile>,
-    pub(crate) get_sections: Symbol<'lib, LLVMPY_GetSections>,
-    pub(crate) dispose_section_iterator: Symbol<'lib, LLVMPY_DisposeSectionIterator>,
-    pub(crate) move_to_next_section: Symbol<'lib, LLVMPY_MoveToNextSection>,
-    pub(crate) is_section_iterator_at_end: Symbol<'lib, LLVMPY_IsSectionIteratorAtEnd>,
-    pub(crate) get_section_name: Symbol<'lib, LLVMPY_GetSectionName>,
-    pub(crate) get_section_address: Symbol<'lib, LLVMPY_GetSectionAddress>,
-    pub(crate) get_section_contents: Symbol<'lib, LLVMPY_GetSectionContents>,
-    pub(crate) get_section_size: Symbol<'lib, LLVMPY_GetSectionSize>,
-    pub(crate) is_section_text: Symbol<'lib, LLVMPY_IsSectionText>,
-    pub(crate) set_time_passes: Symbol<'lib, LLVMPY_SetTimePasses>,
-    pub(crate) report_and_reset_timings: Symbol<'lib, LLVMPY_ReportAndResetTimings>,
-    pub(crate) create_pass_manager: Symbol<'lib, LLVMPY_CreatePassManager>,
-    pub(crate) dispose_pass_manager: Symbol<'lib, LLVMPY_DisposePassManager>,
-    pub(crate) create_function_pass_manager: Symbol<'lib, LLVMPY_CreateFunctionPassManager>,
-    pub(crate) run_pass_manager_with_remarks: Symbol<'lib, LLVMPY_RunPassManagerWithRemarks>,
-    pub(crate) run_pass_manager: Symbol<'lib, LLVMPY_RunPassManager>,
-    pub(crate) run_function_pass_manager_with_remarks: Symbol<'lib, LLVMPY_RunFunctionPassManagerWithRemarks>,
-    pub(crate) run_function_pass_manager: Symbol<'lib, LLVMPY_RunFunctionPassManager>,
-    pub(crate) initialize_function_pass_manager: Symbol<'lib, LLVMPY_InitializeFunctionPassManager>,
-    pub(crate) finalize_function_pass_manager: Symbol<'lib, LLVMPY_FinalizeFunctionPassManager>,
-    pub(crate) add_a_a_eval_pass: Symbol<'lib, LLVMPY_AddAAEvalPass>,
-    pub(crate) add_basic_a_a_wrapper_pass: Symbol<'lib, LLVMPY_AddBasicAAWrapperPass>,
-    pub(crate) add_dependence_analysis_pass: Symbol<'lib, LLVMPY_AddDependenceAnalysisPass>,
-    pub(crate) add_call_graph_dot_printer_pass: Symbol<'lib, LLVMPY_AddCallGraphDOTPrinterPass>,
-    pub(crate) add_dot_dom_printer_pass: Symbol<'lib, LLVMPY_AddDotDomPrinterPass>,
-    pub(crate) add_globals_mod_ref_a_a_pass: Symbol<'lib, LLVMPY_AddGlobalsModRefAAPass>,
-    pub(crate) add_dot_post_dom_printer_pass: Symbol<'lib, LLVMPY_AddDotPostDomPrinterPass>,
-    pub(crate) add_cfg_printer_pass: Symbol<'lib, LLVMPY_AddCFGPrinterPass>,
-    pub(crate) add_constant_merge_pass: Symbol<'lib, LLVMPY_AddConstantMergePass>,
-    pub(crate) add_dead_store_elimination_pass: Symbol<'lib, LLVMPY_AddDeadStoreEliminationPass>,
-    pub(crate) add_reverse_post_order_function_attrs_pass: Symbol<'lib, LLVMPY_AddReversePostOrderFunctionAttrsPass>,
-    pub(crate) add_dead_arg_elimination_pass: Symbol<'lib, LLVMPY_AddDeadArgEliminationPass>,
-    pub(crate) add_instruction_count_pass: Symbol<'lib, LLVMPY_AddInstructionCountPass>,
-    pub(crate) add_iv_users_pass: Symbol<'lib, LLVMPY_AddIVUsersPass>,
-    pub(crate) add_lazy_value_info_pass: Symbol<'lib, LLVMPY_AddLazyValueInfoPass>,
-    pub(crate) add_lint_pass: Symbol<'lib, LLVMPY_AddLintPass>,
-    pub(crate) add_module_debug_info_printer_pass: Symbol<'lib, LLVMPY_AddModuleDebugInfoPrinterPass>,
-    pub(crate) add_region_info_pass: Symbol<'lib, LLVMPY_AddRegionInfoPass>,
-    pub(crate) add_scalar_evolution_a_a_pass: Symbol<'lib, LLVMPY_AddScalarEvolutionAAPass>,
-    pub(crate) add_aggressive_d_c_e_pass: Symbol<'lib, LLVMPY_AddAggressiveDCEPass>,
-    pub(crate) add_always_inliner_pass: Symbol<'lib, LLVMPY_AddAlwaysInlinerPass>,
-    pub(crate) add_arg_promotion_pass: Symbol<'lib, LLVMPY_AddArgPromotionPass>,
-    pub(crate) add_break_critical_edges_pass: Symbol<'lib, LLVMPY_AddBreakCriticalEdgesPass>,
-    pub(crate) add_function_attrs_pass: Symbol<'lib, LLVMPY_AddFunctionAttrsPass>,
-    pub(crate) add_function_inlining_pass: Symbol<'lib, LLVMPY_AddFunctionInliningPass>,
-    pub(crate) add_global_optimizer_pass: Symbol<'lib, LLVMPY_AddGlobalOptimizerPass>,
-    pub(crate) add_global_dce_pass: Symbol<'lib, LLVMPY_AddGlobalDCEPass>,
-    pub(crate) add_ipsccp_pass: Symbol<'lib, LLVMPY_AddIPSCCPPass>,
-    pub(crate) add_dead_code_elimination_pass: Symbol<'lib, LLVMPY_AddDeadCodeEliminationPass>,
-    pub(crate) add_aggressive_instruction_combining_pass: Symbol<'lib, LLVMPY_AddAggressiveInstructionCombiningPass>,
-    pub(crate) add_internalize_pass: Symbol<'lib, LLVMPY_AddInternalizePass>,
-    pub(crate) add_jump_threading_pass: Symbol<'lib, LLVMPY_AddJumpThreadingPass>,
-    pub(crate) add_lcssa_pass: Symbol<'lib, LLVMPY_AddLCSSAPass>,
-    pub(crate) add_loop_deletion_pass: Symbol<'lib, LLVMPY_AddLoopDeletionPass>,
-    pub(crate) add_single_loop_extractor_pass: Symbol<'lib, LLVMPY_AddSingleLoopExtractorPass>,
-    pub(crate) add_loop_strength_reduce_pass: Symbol<'lib, LLVMPY_AddLoopStrengthReducePass>,
-    pub(crate) add_loop_simplification_pass: Symbol<'lib, LLVMPY_AddLoopSimplificationPass>,
-    pub(crate) add_loop_unroll_pass: Symbol<'lib, LLVMPY_AddLoopUnrollPass>,
-    pub(crate) add_loop_unroll_and_jam_pass: Symbol<'lib, LLVMPY_AddLoopUnrollAndJamPass>,
-    pub(crate) add_loop_unswitch_pass: Symbol<'lib, LLVMPY_AddLoopUnswitchPass>,
-    pub(crate) add_lower_atomic_pass: Symbol<'lib, LLVMPY_AddLowerAtomicPass>,
-    pub(crate) add_lower_invoke_pass: Symbol<'lib, LLVMPY_AddLowerInvokePass>,
-    pub(crate) add_lower_switch_pass: Symbol<'lib, LLVMPY_AddLowerSwitchPass>,
-    pub(crate) add_mem_cpy_optimization_pass: Symbol<'lib, LLVMPY_AddMemCpyOptimizationPass>,
-    pub(crate) add_merge_functions_pass: Symbol<'lib, LLVMPY_AddMergeFunctionsPass>,
-    pub(crate) add_merge_returns_pass: Symbol<'lib, LLVMPY_AddMergeReturnsPass>,
-    pub(crate) add_partial_inlining_pass: Symbol<'lib, LLVMPY_AddPartialInliningPass>,
-    pub(crate) add_prune_exception_handling_pass: Symbol<'lib, LLVMPY_AddPruneExceptionHandlingPass>,
-    pub(crate) add_re_associate_pass: Symbol<'lib, LLVMPY_AddReassociatePass>,
-    pub(crate) add_demote_register_to_memory_pass: Symbol<'lib, LLVMPY_AddDemoteRegisterToMemoryPass>,
-    pub(crate) add_sink_pass: Symbol<'lib, LLVMPY_AddSinkPass>,
-    pub(crate) add_strip_symbols_pass: Symbol<'lib, LLVMPY_AddStripSymbolsPass>,
-    pub(crate) add_strip_dead_debug_info_pass: Symbol<'lib, LLVMPY_AddStripDeadDebugInfoPass>,
-    pub(crate) add_strip_dead_prototypes_pass: Symbol<'lib, LLVMPY_AddStripDeadPrototypesPass>,
-    pub(crate) add_strip_debug_declare_prototypes_pass: Symbol<'lib, LLVMPY_AddStripDebugDeclarePrototypesPass>,
-    pub(crate) add_strip_nondebug_symbols_pass: Symbol<'lib, LLVMPY_AddStripNondebugSymbolsPass>,
-    pub(crate) add_tail_call_elimination_pass: Symbol<'lib, LLVMPY_AddTailCallEliminationPass>,
-    pub(crate) add_cfg_simplification_pass: Symbol<'lib, LLVMPY_AddCFGSimplificationPass>,
-    pub(crate) add_gvn_pass: Symbol<'lib, LLVMPY_AddGVNPass>,
-    pub(crate) add_instruction_combining_pass: Symbol<'lib, LLVMPY_AddInstructionCombiningPass>,
-    pub(crate) add_licm_pass: Symbol<'lib, LLVMPY_AddLICMPass>,
-    pub(crate) add_sccp_pass: Symbol<'lib, LLVMPY_AddSCCPPass>,
-    pub(crate) add_sroa_pass: Symbol<'lib, LLVMPY_AddSROAPass>,
-    pub(crate) add_type_based_alias_analysis_pass: Symbol<'lib, LLVMPY_AddTypeBasedAliasAnalysisPass>,
-    pub(crate) add_basic_alias_analysis_pass: Symbol<'lib, LLVMPY_AddBasicAliasAnalysisPass>,
-    pub(crate) llvm_add_loop_rotate_pass: Symbol<'lib, LLVMPY_LLVMAddLoopRotatePass>,
-    pub(crate) get_process_triple: Symbol<'lib, LLVMPY_GetProcessTriple>,
-    pub(crate) get_host_c_p_u_features: Symbol<'lib, LLVMPY_GetHostCPUFeatures>,
-    pub(crate) get_default_target_triple: Symbol<'lib, LLVMPY_GetDefaultTargetTriple>,
-    pub(crate) get_host_c_p_u_name: Symbol<'lib, LLVMPY_GetHostCPUName>,
-    pub(crate) get_triple_object_format: Symbol<'lib, LLVMPY_GetTripleObjectFormat>,
-    pub(crate) create_target_data: Symbol<'lib, LLVMPY_CreateTargetData>,
-    pub(crate) copy_string_rep_of_target_data: Symbol<'lib, LLVMPY_CopyStringRepOfTargetData>,
-    pub(crate) dispose_target_data: Symbol<'lib, LLVMPY_DisposeTargetData>,
-    pub(crate) abi_size_of_type: Symbol<'lib, LLVMPY_ABISizeOfType>,
-    pub(crate) offset_of_element: Symbol<'lib, LLVMPY_OffsetOfElement>,
-    pub(crate) abi_size_of_element_type: Symbol<'lib, LLVMPY_ABISizeOfElementType>,
-    pub(crate) abi_alignment_of_element_type: Symbol<'lib, LLVMPY_ABIAlignmentOfElementType>,
-    pub(crate) get_target_from_triple: Symbol<'lib, LLVMPY_GetTargetFromTriple>,
-    pub(crate) get_target_name: Symbol<'lib, LLVMPY_GetTargetName>,
-    pub(crate) get_target_description: Symbol<'lib, LLVMPY_GetTargetDescription>,
-    pub(crate) create_target_machine: Symbol<'lib, LLVMPY_CreateTargetMachine>,
-    pub(crate) dispose_target_machine: Symbol<'lib, LLVMPY_DisposeTargetMachine>,
-    pub(crate) get_target_machine_triple: Symbol<'lib, LLVMPY_GetTargetMachineTriple>,
-    pub(crate) set_target_machine_asm_verbosity: Symbol<'lib, LLVMPY_SetTargetMachineAsmVerbosity>,
-    pub(crate) target_machine_emit_to_memory: Symbol<'lib, LLVMPY_TargetMachineEmitToMemory>,
-    pub(crate) create_target_machine_data: Symbol<'lib, LLVMPY_CreateTargetMachineData>,
-    pub(crate) add_analysis_passes: Symbol<'lib, LLVMPY_AddAnalysisPasses>,
-    pub(crate) get_buffer_start: Symbol<'lib, LLVMPY_GetBufferStart>,
-    pub(crate) get_buffer_size: Symbol<'lib, LLVMPY_GetBufferSize>,
-    pub(crate) dispose_memory_buffer: Symbol<'lib, LLVMPY_DisposeMemoryBuffer>,
-    pub(crate) has_svml_support: Symbol<'lib, LLVMPY_HasSVMLSupport>,
-    pub(crate) pass_manager_builder_create: Symbol<'lib, LLVMPY_PassManagerBuilderCreate>,
-    pub(crate) pass_manager_builder_dispose: Symbol<'lib, LLVMPY_PassManagerBuilderDispose>,
-    pub(crate) pass_manager_builder_get_opt_level: Symbol<'lib, LLVMPY_PassManagerBuilderGetOptLevel>,
-    pub(crate) pass_manager_builder_set_opt_level: Symbol<'lib, LLVMPY_PassManagerBuilderSetOptLevel>,
-    pub(crate) pass_manager_builder_get_size_level: Symbol<'lib, LLVMPY_PassManagerBuilderGetSizeLevel>,
-    pub(crate) pass_manager_builder_set_size_level: Symbol<'lib, LLVMPY_PassManagerBuilderSetSizeLevel>,
-    pub(crate) pass_manager_builder_get_disable_unroll_loops: Symbol<'lib, LLVMPY_PassManagerBuilderGetDisableUnrollLoops>,
-    pub(crate) pass_manager_builder_set_disable_unroll_loops: Symbol<'lib, LLVMPY_PassManagerBuilderSetDisableUnrollLoops>,
-    pub(crate) pass_manager_builder_use_inliner_with_threshold: Symbol<'lib, LLVMPY_PassManagerBuilderUseInlinerWithThreshold>,
-    pub(crate) pass_manager_builder_populate_function_pass_manager: Symbol<'lib, LLVMPY_PassManagerBuilderPopulateFunctionPassManager>,
-    pub(crate) pass_manager_builder_set_loop_vectorize: Symbol<'lib, LLVMPY_PassManagerBuilderSetLoopVectorize>,
-    pub(crate) pass_manager_builder_get_loop_vectorize: Symbol<'lib, LLVMPY_PassManagerBuilderGetLoopVectorize>,
-    pub(crate) pass_manager_builder_set_s_l_p_vectorize: Symbol<'lib, LLVMPY_PassManagerBuilderSetSLPVectorize>,
-    pub(crate) pass_manager_builder_get_s_l_p_vectorize: Symbol<'lib, LLVMPY_PassManagerBuilderGetSLPVectorize>,
-    pub(crate) function_attributes_iter: Symbol<'lib, LLVMPY_FunctionAttributesIter>,
-    pub(crate) argument_attributes_iter: Symbol<'lib, LLVMPY_ArgumentAttributesIter>,
-    pub(crate) call_inst_attributes_iter: Symbol<'lib, LLVMPY_CallInstAttributesIter>,
-    pub(crate) invoke_inst_attributes_iter: Symbol<'lib, LLVMPY_InvokeInstAttributesIter>,
-    pub(crate) global_attributes_iter: Symbol<'lib, LLVMPY_GlobalAttributesIter>,
-    pub(crate) function_blocks_iter: Symbol<'lib, LLVMPY_FunctionBlocksIter>,
-    pub(crate) function_arguments_iter: Symbol<'lib, LLVMPY_FunctionArgumentsIter>,
-    pub(crate) block_instructions_iter: Symbol<'lib, LLVMPY_BlockInstructionsIter>,
-    pub(crate) instruction_operands_iter: Symbol<'lib, LLVMPY_InstructionOperandsIter>,
-    pub(crate) attribute_list_iter_next: Symbol<'lib, LLVMPY_AttributeListIterNext>,
-    pub(crate) blocks_iter_next: Symbol<'lib, LLVMPY_BlocksIterNext>,
-    pub(crate) arguments_iter_next: Symbol<'lib, LLVMPY_ArgumentsIterNext>,
-    pub(crate) instructions_iter_next: Symbol<'lib, LLVMPY_InstructionsIterNext>,
-    pub(crate) operands_iter_next: Symbol<'lib, LLVMPY_OperandsIterNext>,
-    pub(crate) dispose_attribute_list_iter: Symbol<'lib, LLVMPY_DisposeAttributeListIter>,
-    pub(crate) dispose_attribute_set_iter: Symbol<'lib, LLVMPY_DisposeAttributeSetIter>,
-    pub(crate) dispose_blocks_iter: Symbol<'lib, LLVMPY_DisposeBlocksIter>,
-    pub(crate) dispose_arguments_iter: Symbol<'lib, LLVMPY_DisposeArgumentsIter>,
-    pub(crate) dispose_instructions_iter: Symbol<'lib, LLVMPY_DisposeInstructionsIter>,
-    pub(crate) dispose_operands_iter: Symbol<'lib, LLVMPY_DisposeOperandsIter>,
-    pub(crate) print_value_to_string: Symbol<'lib, LLVMPY_PrintValueToString>,
-    pub(crate) get_value_name: Symbol<'lib, LLVMPY_GetValueName>,
-    pub(crate) set_value_name: Symbol<'lib, LLVMPY_SetValueName>,
-    pub(crate) get_global_parent: Symbol<'lib, LLVMPY_GetGlobalParent>,
-    pub(crate) type_of: Symbol<'lib, LLVMPY_TypeOf>,
-    pub(crate) print_type: Symbol<'lib, LLVMPY_PrintType>,
-    pub(crate) get_type_name: Symbol<'lib, LLVMPY_GetTypeName>,
-    pub(crate) type_is_pointer: Symbol<'lib, LLVMPY_TypeIsPointer>,
-    pub(crate) get_element_type: Symbol<'lib, LLVMPY_GetElementType>,
-    pub(crate) set_linkage: Symbol<'lib, LLVMPY_SetLinkage>,
-    pub(crate) get_linkage: Symbol<'lib, LLVMPY_GetLinkage>,
-    pub(crate) set_visibility: Symbol<'lib, LLVMPY_SetVisibility>,
-    pub(crate) get_visibility: Symbol<'lib, LLVMPY_GetVisibility>,
-    pub(crate) set_dll_storage_class: Symbol<'lib, LLVMPY_SetDLLStorageClass>,
-    pub(crate) get_dll_storage_class: Symbol<'lib, LLVMPY_GetDLLStorageClass>,
-    pub(crate) get_enum_attribute_kind_for_name: Symbol<'lib, LLVMPY_GetEnumAttributeKindForName>,
-    pub(crate) add_function_attr: Symbol<'lib, LLVMPY_AddFunctionAttr>,
-    pub(crate) is_declaration: Symbol<'lib, LLVMPY_IsDeclaration>,
-    pub(crate) write_cfg: Symbol<'lib, LLVMPY_WriteCFG>,
-    pub(crate) get_opcode_name: Symbol<'lib, LLVMPY_GetOpcodeName>,
+pub(crate) struct OptionalSymbol<T> {
+    name: &'static str,
+    capability: Capability,
+    inner: Option<T>,
 }
 
-impl<'lib> LLVMMethods<'lib> {
-    unsafe fn load(lib: &'lib Library) -> Result<Self, LoadError> {
-        Ok(Self {
-            set_cmd_line: load_fn(lib, b"LLVMPY_SetCommandLine")?,
-            parse_assembly: load_fn(lib, b"LLVMPY_ParseAssembly")?,
-            write_bitcode_to_string: load_fn(lib, b"LLVMPY_WriteBitcodeToString")?,
-            parse_bitcode: load_fn(lib, b"LLVMPY_ParseBitcode")?,
-            create_string: load_fn(lib, b"LLVMPY_CreateString")?,
-            create_byte_string: load_fn(lib, b"LLVMPY_CreateByteString")?,
-            dispose_string: load_fn(lib, b"LLVMPY_DisposeString")?,
-            get_global_context: load_fn(lib, b"LLVMPY_GetGlobalContext")?,
-            context_create: load_fn(lib, b"LLVMPY_ContextCreate")?,
-            context_dispose: load_fn(lib, b"LLVMPY_ContextDispose")?,
-            add_ref_prune_pass: load_fn(lib, b"LLVMPY_AddRefPrunePass")?,
-            dump_ref_prune_stats: load_fn(lib, b"LLVMPY_DumpRefPruneStats")?,
-            search_address_of_symbol: load_fn(lib, b"LLVMPY_SearchAddressOfSymbol")?,
-            add_symbol: load_fn(lib, b"LLVMPY_AddSymbol")?,
-            load_library_permanently: load_fn(lib, b"LLVMPY_LoadLibraryPermanently")?,
-            link_in_mc_jit: load_fn(lib, b"LLVMPY_LinkInMCJIT")?,
-            dispose_execution_engine: load_fn(lib, b"LLVMPY_DisposeExecutionEngine")?,
-            add_module: load_fn(lib, b"LLVMPY_AddModule")?,
-            remove_module: load_fn(lib, b"LLVMPY_RemoveModule")?,
-            finalize_object: load_fn(lib, b"LLVMPY_FinalizeObject")?,
-            create_mc_jit_compiler: load_fn(lib, b"LLVMPY_CreateMCJITCompiler")?,
-            get_global_value_address: load_fn(lib, b"LLVMPY_GetGlobalValueAddress")?,
-            get_function_address: load_fn(lib, b"LLVMPY_GetFunctionAddress")?,
-            run_static_constructors: load_fn(lib, b"LLVMPY_RunStaticConstructors")?,
-            run_static_destructors: load_fn(lib, b"LLVMPY_RunStaticDestructors")?,
-            add_global_mapping: load_fn(lib, b"LLVMPY_AddGlobalMapping")?,
-            get_execution_engine_target_data: load_fn(lib, b"LLVMPY_GetExecutionEngineTargetData")?,
-            try_allocate_executable_memory: load_fn(lib, b"LLVMPY_TryAllocateExecutableMemory")?,
-            enable_jit_events: load_fn(lib, b"LLVMPY_EnableJITEvents")?,
-            mc_jit_add_object_file: load_fn(lib, b"LLVMPY_MCJITAddObjectFile")?,
-            create_object_cache: load_fn(lib, b"LLVMPY_CreateObjectCache")?,
-            dispose_object_cache: load_fn(lib, b"LLVMPY_DisposeObjectCache")?,
-            set_object_cache: load_fn(lib, b"LLVMPY_SetObjectCache")?,
-            shutdown: load_fn(lib, b"LLVMPY_Shutdown")?,
-            get_version_info: load_fn(lib, b"LLVMPY_GetVersionInfo")?,
-            link_modules: load_fn(lib, b"LLVMPY_LinkModules")?,
-            dispose_module: load_fn(lib, b"LLVMPY_DisposeModule")?,
-            print_module_to_string: load_fn(lib, b"LLVMPY_PrintModuleToString")?,
-            get_module_source_file_name: load_fn(lib, b"LLVMPY_GetModuleSourceFileName")?,
-            get_module_name: load_fn(lib, b"LLVMPY_GetModuleName")?,
-            set_module_name: load_fn(lib, b"LLVMPY_SetModuleName")?,
-            get_named_function: load_fn(lib, b"LLVMPY_GetNamedFunction")?,
-            get_named_global_variable: load_fn(lib, b"LLVMPY_GetNamedGlobalVariable")?,
-            get_named_struct_type: load_fn(lib, b"LLVMPY_GetNamedStructType")?,
-            verify_module: load_fn(lib, b"LLVMPY_VerifyModule")?,
-            get_data_layout: load_fn(lib, b"LLVMPY_GetDataLayout")?,
-            set_data_layout: load_fn(lib, b"LLVMPY_SetDataLayout")?,
-            get_target: load_fn(lib, b"LLVMPY_GetTarget")?,
-            set_target: load_fn(lib, b"LLVMPY_SetTarget")?,
-            module_globals_iter: load_fn(lib, b"LLVMPY_ModuleGlobalsIter")?,
-            module_functions_iter: load_fn(lib, b"LLVMPY_ModuleFunctionsIter")?,
-            module_types_iter: load_fn(lib, b"LLVMPY_ModuleTypesIter")?,
-            globals_iter_next: load_fn(lib, b"LLVMPY_GlobalsIterNext")?,
-            functions_iter_next: load_fn(lib, b"LLVMPY_FunctionsIterNext")?,
-            types_iter_next: load_fn(lib, b"LLVMPY_TypesIterNext")?,
-            dispose_globals_iter: load_fn(lib, b"LLVMPY_DisposeGlobalsIter")?,
-            dispose_functions_iter: load_fn(lib, b"LLVMPY_DisposeFunctionsIter")?,
-            dispose_types_iter: load_fn(lib, b"LLVMPY_DisposeTypesIter")?,
-            clone_module: load_fn(lib, b"LLVMPY_CloneModule")?,
-            create_object_file: load_fn(lib, b"LLVMPY_CreateObjectFile")?,
-            dispose_object_file: load_fn(lib, b"LLVMPY_DisposeObjectFile")?,
-            get_sections: load_fn(lib, b"LLVMPY_GetSections")?,
-            dispose_section_iterator: load_fn(lib, b"LLVMPY_DisposeSectionIterator")?,
-            move_to_next_section: load_fn(lib, b"LLVMPY_MoveToNextSection")?,
-            is_section_iterator_at_end: load_fn(lib, b"LLVMPY_IsSectionIteratorAtEnd")?,
-            get_section_name: load_fn(lib, b"LLVMPY_GetSectionName")?,
-            get_section_address: load_fn(lib, b"LLVMPY_GetSectionAddress")?,
-            get_section_contents: load_fn(lib, b"LLVMPY_GetSectionContents")?,
-            get_section_size: load_fn(lib, b"LLVMPY_GetSectionSize")?,
-            is_section_text: load_fn(lib, b"LLVMPY_IsSectionText")?,
-            set_time_passes: load_fn(lib, b"LLVMPY_SetTimePasses")?,
-            report_and_reset_timings: load_fn(lib, b"LLVMPY_ReportAndResetTimings")?,
-            create_pass_manager: load_fn(lib, b"LLVMPY_CreatePassManager")?,
-            dispose_pass_manager: load_fn(lib, b"LLVMPY_DisposePassManager")?,
-            create_function_pass_manager: load_fn(lib, b"LLVMPY_CreateFunctionPassManager")?,
-            run_pass_manager_with_remarks: load_fn(lib, b"LLVMPY_RunPassManagerWithRemarks")?,
-            run_pass_manager: load_fn(lib, b"LLVMPY_RunPassManager")?,
-            run_function_pass_manager_with_remarks: load_fn(lib, b"LLVMPY_RunFunctionPassManagerWithRemarks")?,
-            run_function_pass_manager: load_fn(lib, b"LLVMPY_RunFunctionPassManager")?,
-            initialize_function_pass_manager: load_fn(lib, b"LLVMPY_InitializeFunctionPassManager")?,
-            finalize_function_pass_manager: load_fn(lib, b"LLVMPY_FinalizeFunctionPassManager")?,
-            add_a_a_eval_pass: load_fn(lib, b"LLVMPY_AddAAEvalPass")?,
-            add_basic_a_a_wrapper_pass: load_fn(lib, b"LLVMPY_AddBasicAAWrapperPass")?,
-            add_dependence_analysis_pass: load_fn(lib, b"LLVMPY_AddDependenceAnalysisPass")?,
-            add_call_graph_dot_printer_pass: load_fn(lib, b"LLVMPY_AddCallGraphDOTPrinterPass")?,
-            add_dot_dom_printer_pass: load_fn(lib, b"LLVMPY_AddDotDomPrinterPass")?,
-            add_globals_mod_ref_a_a_pass: load_fn(lib, b"LLVMPY_AddGlobalsModRefAAPass")?,
-            add_dot_post_dom_printer_pass: load_fn(lib, b"LLVMPY_AddDotPostDomPrinterPass")?,
-            add_cfg_printer_pass: load_fn(lib, b"LLVMPY_AddCFGPrinterPass")?,
-            add_constant_merge_pass: load_fn(lib, b"LLVMPY_AddConstantMergePass")?,
-            add_dead_store_elimination_pass: load_fn(lib, b"LLVMPY_AddDeadStoreEliminationPass")?,
-            add_reverse_post_order_function_attrs_pass: load_fn(lib, b"LLVMPY_AddReversePostOrderFunctionAttrsPass")?,
-            add_dead_arg_elimination_pass: load_fn(lib, b"LLVMPY_AddDeadArgEliminationPass")?,
-            add_instruction_count_pass: load_fn(lib, b"LLVMPY_AddInstructionCountPass")?,
-            add_iv_users_pass: load_fn(lib, b"LLVMPY_AddIVUsersPass")?,
-            add_lazy_value_info_pass: load_fn(lib, b"LLVMPY_AddLazyValueInfoPass")?,
-            add_lint_pass: load_fn(lib, b"LLVMPY_AddLintPass")?,
-            add_module_debug_info_printer_pass: load_fn(lib, b"LLVMPY_AddModuleDebugInfoPrinterPass")?,
-            add_region_info_pass: load_fn(lib, b"LLVMPY_AddRegionInfoPass")?,
-            add_scalar_evolution_a_a_pass: load_fn(lib, b"LLVMPY_AddScalarEvolutionAAPass")?,
-            add_aggressive_d_c_e_pass: load_fn(lib, b"LLVMPY_AddAggressiveDCEPass")?,
-            add_always_inliner_pass: load_fn(lib, b"LLVMPY_AddAlwaysInlinerPass")?,
-            add_arg_promotion_pass: load_fn(lib, b"LLVMPY_AddArgPromotionPass")?,
-            add_break_critical_edges_pass: load_fn(lib, b"LLVMPY_AddBreakCriticalEdgesPass")?,
-            add_function_attrs_pass: load_fn(lib, b"LLVMPY_AddFunctionAttrsPass")?,
-            add_function_inlining_pass: load_fn(lib, b"LLVMPY_AddFunctionInliningPass")?,
-            add_global_optimizer_pass: load_fn(lib, b"LLVMPY_AddGlobalOptimizerPass")?,
-            add_global_dce_pass: load_fn(lib, b"LLVMPY_AddGlobalDCEPass")?,
-            add_ipsccp_pass: load_fn(lib, b"LLVMPY_AddIPSCCPPass")?,
-            add_dead_code_elimination_pass: load_fn(lib, b"LLVMPY_AddDeadCodeEliminationPass")?,
-            add_aggressive_instruction_combining_pass: load_fn(lib, b"LLVMPY_AddAggressiveInstructionCombiningPass")?,
-            add_internalize_pass: load_fn(lib, b"LLVMPY_AddInternalizePass")?,
-            add_jump_threading_pass: load_fn(lib, b"LLVMPY_AddJumpThreadingPass")?,
-            add_lcssa_pass: load_fn(lib, b"LLVMPY_AddLCSSAPass")?,
-            add_loop_deletion_pass: load_fn(lib, b"LLVMPY_AddLoopDeletionPass")?,
-            add_single_loop_extractor_pass: load_fn(lib, b"LLVMPY_AddSingleLoopExtractorPass")?,
-            add_loop_strength_reduce_pass: load_fn(lib, b"LLVMPY_AddLoopStrengthReducePass")?,
-            add_loop_simplification_pass: load_fn(lib, b"LLVMPY_AddLoopSimplificationPass")?,
-            add_loop_unroll_pass: load_fn(lib, b"LLVMPY_AddLoopUnrollPass")?,
-            add_loop_unroll_and_jam_pass: load_fn(lib, b"LLVMPY_AddLoopUnrollAndJamPass")?,
-            add_loop_unswitch_pass: load_fn(lib, b"LLVMPY_AddLoopUnswitchPass")?,
-            add_lower_atomic_pass: load_fn(lib, b"LLVMPY_AddLowerAtomicPass")?,
-            add_lower_invoke_pass: load_fn(lib, b"LLVMPY_AddLowerInvokePass")?,
-            add_lower_switch_pass: load_fn(lib, b"LLVMPY_AddLowerSwitchPass")?,
-            add_mem_cpy_optimization_pass: load_fn(lib, b"LLVMPY_AddMemCpyOptimizationPass")?,
-            add_merge_functions_pass: load_fn(lib, b"LLVMPY_AddMergeFunctionsPass")?,
-            add_merge_returns_pass: load_fn(lib, b"LLVMPY_AddMergeReturnsPass")?,
-            add_partial_inlining_pass: load_fn(lib, b"LLVMPY_AddPartialInliningPass")?,
-            add_prune_exception_handling_pass: load_fn(lib, b"LLVMPY_AddPruneExceptionHandlingPass")?,
-            add_re_associate_pass: load_fn(lib, b"LLVMPY_AddReassociatePass")?,
-            add_demote_register_to_memory_pass: load_fn(lib, b"LLVMPY_AddDemoteRegisterToMemoryPass")?,
-            add_sink_pass: load_fn(lib, b"LLVMPY_AddSinkPass")?,
-            add_strip_symbols_pass: load_fn(lib, b"LLVMPY_AddStripSymbolsPass")?,
-            add_strip_dead_debug_info_pass: load_fn(lib, b"LLVMPY_AddStripDeadDebugInfoPass")?,
-            add_strip_dead_prototypes_pass: load_fn(lib, b"LLVMPY_AddStripDeadPrototypesPass")?,
-            add_strip_debug_declare_prototypes_pass: load_fn(lib, b"LLVMPY_AddStripDebugDeclarePrototypesPass")?,
-            add_strip_nondebug_symbols_pass: load_fn(lib, b"LLVMPY_AddStripNondebugSymbolsPass")?,
-            add_tail_call_elimination_pass: load_fn(lib, b"LLVMPY_AddTailCallEliminationPass")?,
-            add_cfg_simplification_pass: load_fn(lib, b"LLVMPY_AddCFGSimplificationPass")?,
-            add_gvn_pass: load_fn(lib, b"LLVMPY_AddGVNPass")?,
-            add_instruction_combining_pass: load_fn(lib, b"LLVMPY_AddInstructionCombiningPass")?,
-            add_licm_pass: load_fn(lib, b"LLVMPY_AddLICMPass")?,
-            add_sccp_pass: load_fn(lib, b"LLVMPY_AddSCCPPass")?,
-            add_sroa_pass: load_fn(lib, b"LLVMPY_AddSROAPass")?,
-            add_type_based_alias_analysis_pass: load_fn(lib, b"LLVMPY_AddTypeBasedAliasAnalysisPass")?,
-            add_basic_alias_analysis_pass: load_fn(lib, b"LLVMPY_AddBasicAliasAnalysisPass")?,
-            llvm_add_loop_rotate_pass: load_fn(lib, b"LLVMPY_LLVMAddLoopRotatePass")?,
-            get_process_triple: load_fn(lib, b"LLVMPY_GetProcessTriple")?,
-            get_host_c_p_u_features: load_fn(lib, b"LLVMPY_GetHostCPUFeatures")?,
-            get_default_target_triple: load_fn(lib, b"LLVMPY_GetDefaultTargetTriple")?,
-            get_host_c_p_u_name: load_fn(lib, b"LLVMPY_GetHostCPUName")?,
-            get_triple_object_format: load_fn(lib, b"LLVMPY_GetTripleObjectFormat")?,
-            create_target_data: load_fn(lib, b"LLVMPY_CreateTargetData")?,
-            copy_string_rep_of_target_data: load_fn(lib, b"LLVMPY_CopyStringRepOfTargetData")?,
-            dispose_target_data: load_fn(lib, b"LLVMPY_DisposeTargetData")?,
-            abi_size_of_type: load_fn(lib, b"LLVMPY_ABISizeOfType")?,
-            offset_of_element: load_fn(lib, b"LLVMPY_OffsetOfElement")?,
-            abi_size_of_element_type: load_fn(lib, b"LLVMPY_ABISizeOfElementType")?,
-            abi_alignment_of_element_type: load_fn(lib, b"LLVMPY_ABIAlignmentOfElementType")?,
-            get_target_from_triple: load_fn(lib, b"LLVMPY_GetTargetFromTriple")?,
-            get_target_name: load_fn(lib, b"LLVMPY_GetTargetName")?,
-            get_target_description: load_fn(lib, b"LLVMPY_GetTargetDescription")?,
-            create_target_machine: load_fn(lib, b"LLVMPY_CreateTargetMachine")?,
-            dispose_target_machine: load_fn(lib, b"LLVMPY_DisposeTargetMachine")?,
-            get_target_machine_triple: load_fn(lib, b"LLVMPY_GetTargetMachineTriple")?,
-            set_target_machine_asm_verbosity: load_fn(lib, b"LLVMPY_SetTargetMachineAsmVerbosity")?,
-            target_machine_emit_to_memory: load_fn(lib, b"LLVMPY_TargetMachineEmitToMemory")?,
-            create_target_machine_data: load_fn(lib, b"LLVMPY_CreateTargetMachineData")?,
-            add_analysis_passes: load_fn(lib, b"LLVMPY_AddAnalysisPasses")?,
-            get_buffer_start: load_fn(lib, b"LLVMPY_GetBufferStart")?,
-            get_buffer_size: load_fn(lib, b"LLVMPY_GetBufferSize")?,
-            dispose_memory_buffer: load_fn(lib, b"LLVMPY_DisposeMemoryBuffer")?,
-            has_svml_support: load_fn(lib, b"LLVMPY_HasSVMLSupport")?,
-            pass_manager_builder_create: load_fn(lib, b"LLVMPY_PassManagerBuilderCreate")?,
-            pass_manager_builder_dispose: load_fn(lib, b"LLVMPY_PassManagerBuilderDispose")?,
-            pass_manager_builder_get_opt_level: load_fn(lib, b"LLVMPY_PassManagerBuilderGetOptLevel")?,
-            pass_manager_builder_set_opt_level: load_fn(lib, b"LLVMPY_PassManagerBuilderSetOptLevel")?,
-            pass_manager_builder_get_size_level: load_fn(lib, b"LLVMPY_PassManagerBuilderGetSizeLevel")?,
-            pass_manager_builder_set_size_level: load_fn(lib, b"LLVMPY_PassManagerBuilderSetSizeLevel")?,
-            pass_manager_builder_get_disable_unroll_loops: load_fn(lib, b"LLVMPY_PassManagerBuilderGetDisableUnrollLoops")?,
-            pass_manager_builder_set_disable_unroll_loops: load_fn(lib, b"LLVMPY_PassManagerBuilderSetDisableUnrollLoops")?,
-            pass_manager_builder_use_inliner_with_threshold: load_fn(lib, b"LLVMPY_PassManagerBuilderUseInlinerWithThreshold")?,
-            pass_manager_builder_populate_function_pass_manager: load_fn(lib, b"LLVMPY_PassManagerBuilderPopulateFunctionPassManager")?,
-            pass_manager_builder_set_loop_vectorize: load_fn(lib, b"LLVMPY_PassManagerBuilderSetLoopVectorize")?,
-            pass_manager_builder_get_loop_vectorize: load_fn(lib, b"LLVMPY_PassManagerBuilderGetLoopVectorize")?,
-            pass_manager_builder_set_s_l_p_vectorize: load_fn(lib, b"LLVMPY_PassManagerBuilderSetSLPVectorize")?,
-            pass_manager_builder_get_s_l_p_vectorize: load_fn(lib, b"LLVMPY_PassManagerBuilderGetSLPVectorize")?,
-            function_attributes_iter: load_fn(lib, b"LLVMPY_FunctionAttributesIter")?,
-            argument_attributes_iter: load_fn(lib, b"LLVMPY_ArgumentAttributesIter")?,
-            call_inst_attributes_iter: load_fn(lib, b"LLVMPY_CallInstAttributesIter")?,
-            invoke_inst_attributes_iter: load_fn(lib, b"LLVMPY_InvokeInstAttributesIter")?,
-            global_attributes_iter: load_fn(lib, b"LLVMPY_GlobalAttributesIter")?,
-            function_blocks_iter: load_fn(lib, b"LLVMPY_FunctionBlocksIter")?,
-            function_arguments_iter: load_fn(lib, b"LLVMPY_FunctionArgumentsIter")?,
-            block_instructions_iter: load_fn(lib, b"LLVMPY_BlockInstructionsIter")?,
-            instruction_operands_iter: load_fn(lib, b"LLVMPY_InstructionOperandsIter")?,
-            attribute_list_iter_next: load_fn(lib, b"LLVMPY_AttributeListIterNext")?,
-            blocks_iter_next: load_fn(lib, b"LLVMPY_BlocksIterNext")?,
-            arguments_iter_next: load_fn(lib, b"LLVMPY_ArgumentsIterNext")?,
-            instructions_iter_next: load_fn(lib, b"LLVMPY_InstructionsIterNext")?,
-            operands_iter_next: load_fn(lib, b"LLVMPY_OperandsIterNext")?,
-            dispose_attribute_list_iter: load_fn(lib, b"LLVMPY_DisposeAttributeListIter")?,
-            dispose_attribute_set_iter: load_fn(lib, b"LLVMPY_DisposeAttributeSetIter")?,
-            dispose_blocks_iter: load_fn(lib, b"LLVMPY_DisposeBlocksIter")?,
-            dispose_arguments_iter: load_fn(lib, b"LLVMPY_DisposeArgumentsIter")?,
-            dispose_instructions_iter: load_fn(lib, b"LLVMPY_DisposeInstructionsIter")?,
-            dispose_operands_iter: load_fn(lib, b"LLVMPY_DisposeOperandsIter")?,
-            print_value_to_string: load_fn(lib, b"LLVMPY_PrintValueToString")?,
-            get_value_name: load_fn(lib, b"LLVMPY_GetValueName")?,
-            set_value_name: load_fn(lib, b"LLVMPY_SetValueName")?,
-            get_global_parent: load_fn(lib, b"LLVMPY_GetGlobalParent")?,
-            type_of: load_fn(lib, b"LLVMPY_TypeOf")?,
-            print_type: load_fn(lib, b"LLVMPY_PrintType")?,
-            get_type_name: load_fn(lib, b"LLVMPY_GetTypeName")?,
-            type_is_pointer: load_fn(lib, b"LLVMPY_TypeIsPointer")?,
-            get_element_type: load_fn(lib, b"LLVMPY_GetElementType")?,
-            set_linkage: load_fn(lib, b"LLVMPY_SetLinkage")?,
-            get_linkage: load_fn(lib, b"LLVMPY_GetLinkage")?,
-            set_visibility: load_fn(lib, b"LLVMPY_SetVisibility")?,
-            get_visibility: load_fn(lib, b"LLVMPY_GetVisibility")?,
-            set_dll_storage_class: load_fn(lib, b"LLVMPY_SetDLLStorageClass")?,
-            get_dll_storage_class: load_fn(lib, b"LLVMPY_GetDLLStorageClass")?,
-            get_enum_attribute_kind_for_name: load_fn(lib, b"LLVMPY_GetEnumAttributeKindForName")?,
-            add_function_attr: load_fn(lib, b"LLVMPY_AddFunctionAttr")?,
-            is_declaration: load_fn(lib, b"LLVMPY_IsDeclaration")?,
-            write_cfg: load_fn(lib, b"LLVMPY_WriteCFG")?,
-            get_opcode_name: load_fn(lib, b"LLVMPY_GetOpcodeName")?,
+impl<T: Copy> OptionalSymbol<T> {
+    /// Gets the symbol or a [MissingSymbol] error if the library does not provide it.
+    pub(crate) fn get(&self) -> Result<T, MissingSymbol> {
+        self.inner.ok_or(MissingSymbol {
+            name: self.name,
+            capability: self.capability,
         })
     }
 }
 
-unsafe fn load_fn<'lib, T>(lib: &'lib Library, name: &[u8]) -> Result<Symbol<'lib, T>, LoadError> {
-    let pretty_name = String::from_utf8_lossy(name);
+/// Generates [LLVMMethods] and its constructors from a single list of symbols.
+///
+/// Each entry maps a field to its `LLVMPY_*` type, whose name is also the name of
+/// the symbol. Symbols followed by a [Capability] are optional and stored as an
+/// [OptionalSymbol].
+macro_rules! llvm_methods {
+    (
+        $(#[$attr:meta])*
+        pub struct LLVMMethods {
+            $($field:ident: $symbol:ident $(=> $capability:expr)?,)*
+        }
+    ) => {
+        $(#[$attr])*
+        pub struct LLVMMethods {
+            $(pub(crate) $field: symbol_type!($symbol $(, $capability)?),)*
+            pub(crate) unavailable: Vec<Capability>,
+            // NOTE: Must never be unloaded while the function pointers above are reachable.
+            // This is `None` when the library was linked at build time.
+            _library: Option<Library>,
+        }
+
+        impl LLVMMethods {
+            /// Every symbol in the table and the capability it belongs to, if optional.
+            const SYMBOLS: &'static [(&'static str, Option<Capability>)] = &[
+                $((stringify!($symbol), optional_capability!($($capability)?)),)*
+            ];
+
+            /// Loads every symbol, returning the names of all the required
+            /// symbols which are missing if there are any.
+            unsafe fn load(library: Library) -> Result<Self, Vec<&'static str>> {
+                let lib = &library;
+                let mut unavailable = Vec::new();
+                let mut missing = Vec::new();
+
+                $(let $field = load_symbol!(
+                    lib,
+                    unavailable,
+                    missing,
+                    $symbol
+                    $(, $capability)?
+                );)*
+
+                if !missing.is_empty() {
+                    return Err(missing);
+                }
+
+                Ok(Self {
+                    $($field: loaded_symbol!($field $(, $capability)?),)*
+                    unavailable,
+                    _library: Some(library),
+                })
+            }
+
+            fn symbols(&self) -> Vec<SymbolStatus> {
+                vec![$(SymbolStatus {
+                    name: stringify!($symbol),
+                    capability: optional_capability!($($capability)?),
+                    provided: symbol_provided!(self.$field $(, $capability)?),
+                },)*]
+            }
+
+            #[cfg(feature = "static")]
+            fn linked() -> Self {
+                Self {
+                    $($field: linked_symbol!($symbol $(, $capability)?),)*
+                    unavailable: Vec::new(),
+                    _library: None,
+                }
+            }
+        }
+    };
+}
+
+macro_rules! symbol_type {
+    ($symbol:ident) => { $symbol };
+    ($symbol:ident, $capability:expr) => { OptionalSymbol<$symbol> };
+}
+
+macro_rules! optional_capability {
+    () => { None };
+    ($capability:expr) => { Some($capability) };
+}
+
+macro_rules! load_symbol {
+    ($lib:ident, $unavailable:ident, $missing:ident, $symbol:ident) => {{
+        let symbol: Option<$symbol> = load_fn($lib, stringify!($symbol));
+        if symbol.is_none() {
+            $missing.push(stringify!($symbol));
+        }
+        symbol
+    }};
+    ($lib:ident, $unavailable:ident, $missing:ident, $symbol:ident, $capability:expr) => {
+        load_optional($lib, stringify!($symbol), $capability, &mut $unavailable)
+    };
+}
+
+macro_rules! loaded_symbol {
+    ($field:ident) => {
+        $field.expect("missing symbols are returned before the table is built")
+    };
+    ($field:ident, $capability:expr) => { $field };
+}
+
+macro_rules! symbol_provided {
+    ($field:expr) => { true };
+    ($field:expr, $capability:expr) => { $field.inner.is_some() };
+}
+
+#[cfg(feature = "static")]
+macro_rules! linked_symbol {
+    ($symbol:ident) => { llvmlite_types::linked::$symbol };
+    ($symbol:ident, $capability:expr) => {
+        OptionalSymbol {
+            name: stringify!($symbol),
+            capability: $capability,
+            inner: Some(llvmlite_types::linked::$symbol as $symbol),
+        }
+    };
+}
+
+llvm_methods! {
+    /// The function table of a loaded llvmlite library.
+    ///
+    /// Every symbol is resolved once when the library is loaded and stored as a
+    /// plain function pointer. These pointers are only valid for as long as the
+    /// library stays loaded, which is why the table owns the [Library] itself and
+    /// is only ever handed out behind an [Arc]: any wrapper that may call into the
+    /// library holds a reference to the table, so the library cannot be unloaded
+    /// until the last of them is dropped. When the library is linked at build time
+    /// the pointers are valid for the life of the program instead.
+    ///
+    /// This is the only place the validity of the function pointers is established,
+    /// everything else can rely on it by holding an `Arc<LLVMMethods>`.
+    // Every symbol is loaded up front so a bad library is caught at load time,
+    // even if the safe API does not expose it yet.
+    #[allow(dead_code)]
+    pub struct LLVMMethods {
+        set_cmd_line: LLVMPY_SetCommandLine,
+        parse_assembly: LLVMPY_ParseAssembly,
+        write_bitcode_to_string: LLVMPY_WriteBitcodeToString,
+        parse_bitcode: LLVMPY_ParseBitcode,
+        create_string: LLVMPY_CreateString,
+        create_byte_string: LLVMPY_CreateByteString,
+        dispose_string: LLVMPY_DisposeString,
+        get_global_context: LLVMPY_GetGlobalContext,
+        context_create: LLVMPY_ContextCreate,
+        context_dispose: LLVMPY_ContextDispose,
+        add_ref_prune_pass: LLVMPY_AddRefPrunePass => Capability::RefPrune,
+        dump_ref_prune_stats: LLVMPY_DumpRefPruneStats => Capability::RefPrune,
+        search_address_of_symbol: LLVMPY_SearchAddressOfSymbol,
+        add_symbol: LLVMPY_AddSymbol,
+        load_library_permanently: LLVMPY_LoadLibraryPermanently,
+        link_in_mc_jit: LLVMPY_LinkInMCJIT,
+        dispose_execution_engine: LLVMPY_DisposeExecutionEngine,
+        add_module: LLVMPY_AddModule,
+        remove_module: LLVMPY_RemoveModule,
+        finalize_object: LLVMPY_FinalizeObject,
+        create_mc_jit_compiler: LLVMPY_CreateMCJITCompiler,
+        get_global_value_address: LLVMPY_GetGlobalValueAddress,
+        get_function_address: LLVMPY_GetFunctionAddress,
+        run_static_constructors: LLVMPY_RunStaticConstructors,
+        run_static_destructors: LLVMPY_RunStaticDestructors,
+        add_global_mapping: LLVMPY_AddGlobalMapping,
+        get_execution_engine_target_data: LLVMPY_GetExecutionEngineTargetData,
+        try_allocate_executable_memory: LLVMPY_TryAllocateExecutableMemory,
+        enable_jit_events: LLVMPY_EnableJITEvents => Capability::JitEvents,
+        mc_jit_add_object_file: LLVMPY_MCJITAddObjectFile,
+        create_object_cache: LLVMPY_CreateObjectCache,
+        dispose_object_cache: LLVMPY_DisposeObjectCache,
+        set_object_cache: LLVMPY_SetObjectCache,
+        shutdown: LLVMPY_Shutdown,
+        get_version_info: LLVMPY_GetVersionInfo,
+        link_modules: LLVMPY_LinkModules,
+        dispose_module: LLVMPY_DisposeModule,
+        print_module_to_string: LLVMPY_PrintModuleToString,
+        get_module_source_file_name: LLVMPY_GetModuleSourceFileName,
+        get_module_name: LLVMPY_GetModuleName,
+        set_module_name: LLVMPY_SetModuleName,
+        get_named_function: LLVMPY_GetNamedFunction,
+        get_named_global_variable: LLVMPY_GetNamedGlobalVariable,
+        get_named_struct_type: LLVMPY_GetNamedStructType,
+        verify_module: LLVMPY_VerifyModule,
+        get_data_layout: LLVMPY_GetDataLayout,
+        set_data_layout: LLVMPY_SetDataLayout,
+        get_target: LLVMPY_GetTarget,
+        set_target: LLVMPY_SetTarget,
+        module_globals_iter: LLVMPY_ModuleGlobalsIter,
+        module_functions_iter: LLVMPY_ModuleFunctionsIter,
+        module_types_iter: LLVMPY_ModuleTypesIter,
+        globals_iter_next: LLVMPY_GlobalsIterNext,
+        functions_iter_next: LLVMPY_FunctionsIterNext,
+        types_iter_next: LLVMPY_TypesIterNext,
+        dispose_globals_iter: LLVMPY_DisposeGlobalsIter,
+        dispose_functions_iter: LLVMPY_DisposeFunctionsIter,
+        dispose_types_iter: LLVMPY_DisposeTypesIter,
+        clone_module: LLVMPY_CloneModule,
+        create_object_file: LLVMPY_CreateObjectFile,
+        dispose_object_file: LLVMPY_DisposeObjectFile,
+        get_sections: LLVMPY_GetSections,
+        dispose_section_iterator: LLVMPY_DisposeSectionIterator,
+        move_to_next_section: LLVMPY_MoveToNextSection,
+        is_section_iterator_at_end: LLVMPY_IsSectionIteratorAtEnd,
+        get_section_name: LLVMPY_GetSectionName,
+        get_section_address: LLVMPY_GetSectionAddress,
+        get_section_contents: LLVMPY_GetSectionContents,
+        get_section_size: LLVMPY_GetSectionSize,
+        is_section_text: LLVMPY_IsSectionText,
+        set_time_passes: LLVMPY_SetTimePasses,
+        report_and_reset_timings: LLVMPY_ReportAndResetTimings,
+        create_pass_manager: LLVMPY_CreatePassManager,
+        dispose_pass_manager: LLVMPY_DisposePassManager,
+        create_function_pass_manager: LLVMPY_CreateFunctionPassManager,
+        run_pass_manager_with_remarks: LLVMPY_RunPassManagerWithRemarks,
+        run_pass_manager: LLVMPY_RunPassManager,
+        run_function_pass_manager_with_remarks: LLVMPY_RunFunctionPassManagerWithRemarks,
+        run_function_pass_manager: LLVMPY_RunFunctionPassManager,
+        initialize_function_pass_manager: LLVMPY_InitializeFunctionPassManager,
+        finalize_function_pass_manager: LLVMPY_FinalizeFunctionPassManager,
+        add_a_a_eval_pass: LLVMPY_AddAAEvalPass => Capability::Passes,
+        add_basic_a_a_wrapper_pass: LLVMPY_AddBasicAAWrapperPass => Capability::Passes,
+        add_dependence_analysis_pass: LLVMPY_AddDependenceAnalysisPass => Capability::Passes,
+        add_call_graph_dot_printer_pass: LLVMPY_AddCallGraphDOTPrinterPass => Capability::DotPrinters,
+        add_dot_dom_printer_pass: LLVMPY_AddDotDomPrinterPass => Capability::DotPrinters,
+        add_globals_mod_ref_a_a_pass: LLVMPY_AddGlobalsModRefAAPass => Capability::Passes,
+        add_dot_post_dom_printer_pass: LLVMPY_AddDotPostDomPrinterPass => Capability::DotPrinters,
+        add_cfg_printer_pass: LLVMPY_AddCFGPrinterPass => Capability::DotPrinters,
+        add_constant_merge_pass: LLVMPY_AddConstantMergePass => Capability::Passes,
+        add_dead_store_elimination_pass: LLVMPY_AddDeadStoreEliminationPass => Capability::Passes,
+        add_reverse_post_order_function_attrs_pass: LLVMPY_AddReversePostOrderFunctionAttrsPass => Capability::Passes,
+        add_dead_arg_elimination_pass: LLVMPY_AddDeadArgEliminationPass => Capability::Passes,
+        add_instruction_count_pass: LLVMPY_AddInstructionCountPass => Capability::Passes,
+        add_iv_users_pass: LLVMPY_AddIVUsersPass => Capability::Passes,
+        add_lazy_value_info_pass: LLVMPY_AddLazyValueInfoPass => Capability::Passes,
+        add_lint_pass: LLVMPY_AddLintPass => Capability::Passes,
+        add_module_debug_info_printer_pass: LLVMPY_AddModuleDebugInfoPrinterPass => Capability::Passes,
+        add_region_info_pass: LLVMPY_AddRegionInfoPass => Capability::Passes,
+        add_scalar_evolution_a_a_pass: LLVMPY_AddScalarEvolutionAAPass => Capability::Passes,
+        add_aggressive_d_c_e_pass: LLVMPY_AddAggressiveDCEPass => Capability::Passes,
+        add_always_inliner_pass: LLVMPY_AddAlwaysInlinerPass => Capability::Passes,
+        add_arg_promotion_pass: LLVMPY_AddArgPromotionPass => Capability::Passes,
+        add_break_critical_edges_pass: LLVMPY_AddBreakCriticalEdgesPass => Capability::Passes,
+        add_function_attrs_pass: LLVMPY_AddFunctionAttrsPass => Capability::Passes,
+        add_function_inlining_pass: LLVMPY_AddFunctionInliningPass => Capability::Passes,
+        add_global_optimizer_pass: LLVMPY_AddGlobalOptimizerPass => Capability::Passes,
+        add_global_dce_pass: LLVMPY_AddGlobalDCEPass => Capability::Passes,
+        add_ipsccp_pass: LLVMPY_AddIPSCCPPass => Capability::Passes,
+        add_dead_code_elimination_pass: LLVMPY_AddDeadCodeEliminationPass => Capability::Passes,
+        add_aggressive_instruction_combining_pass: LLVMPY_AddAggressiveInstructionCombiningPass => Capability::Passes,
+        add_internalize_pass: LLVMPY_AddInternalizePass => Capability::Passes,
+        add_jump_threading_pass: LLVMPY_AddJumpThreadingPass => Capability::Passes,
+        add_lcssa_pass: LLVMPY_AddLCSSAPass => Capability::Passes,
+        add_loop_deletion_pass: LLVMPY_AddLoopDeletionPass => Capability::Passes,
+        add_single_loop_extractor_pass: LLVMPY_AddSingleLoopExtractorPass => Capability::Passes,
+        add_loop_strength_reduce_pass: LLVMPY_AddLoopStrengthReducePass => Capability::Passes,
+        add_loop_simplification_pass: LLVMPY_AddLoopSimplificationPass => Capability::Passes,
+        add_loop_unroll_pass: LLVMPY_AddLoopUnrollPass => Capability::Passes,
+        add_loop_unroll_and_jam_pass: LLVMPY_AddLoopUnrollAndJamPass => Capability::Passes,
+        add_loop_unswitch_pass: LLVMPY_AddLoopUnswitchPass => Capability::Passes,
+        add_lower_atomic_pass: LLVMPY_AddLowerAtomicPass => Capability::Passes,
+        add_lower_invoke_pass: LLVMPY_AddLowerInvokePass => Capability::Passes,
+        add_lower_switch_pass: LLVMPY_AddLowerSwitchPass => Capability::Passes,
+        add_mem_cpy_optimization_pass: LLVMPY_AddMemCpyOptimizationPass => Capability::Passes,
+        add_merge_functions_pass: LLVMPY_AddMergeFunctionsPass => Capability::Passes,
+        add_merge_returns_pass: LLVMPY_AddMergeReturnsPass => Capability::Passes,
+        add_partial_inlining_pass: LLVMPY_AddPartialInliningPass => Capability::Passes,
+        add_prune_exception_handling_pass: LLVMPY_AddPruneExceptionHandlingPass => Capability::Passes,
+        add_re_associate_pass: LLVMPY_AddReassociatePass => Capability::Passes,
+        add_demote_register_to_memory_pass: LLVMPY_AddDemoteRegisterToMemoryPass => Capability::Passes,
+        add_sink_pass: LLVMPY_AddSinkPass => Capability::Passes,
+        add_strip_symbols_pass: LLVMPY_AddStripSymbolsPass => Capability::Passes,
+        add_strip_dead_debug_info_pass: LLVMPY_AddStripDeadDebugInfoPass => Capability::Passes,
+        add_strip_dead_prototypes_pass: LLVMPY_AddStripDeadPrototypesPass => Capability::Passes,
+        add_strip_debug_declare_prototypes_pass: LLVMPY_AddStripDebugDeclarePrototypesPass => Capability::Passes,
+        add_strip_nondebug_symbols_pass: LLVMPY_AddStripNondebugSymbolsPass => Capability::Passes,
+        add_tail_call_elimination_pass: LLVMPY_AddTailCallEliminationPass => Capability::Passes,
+        add_cfg_simplification_pass: LLVMPY_AddCFGSimplificationPass => Capability::Passes,
+        add_gvn_pass: LLVMPY_AddGVNPass => Capability::Passes,
+        add_instruction_combining_pass: LLVMPY_AddInstructionCombiningPass => Capability::Passes,
+        add_licm_pass: LLVMPY_AddLICMPass => Capability::Passes,
+        add_sccp_pass: LLVMPY_AddSCCPPass => Capability::Passes,
+        add_sroa_pass: LLVMPY_AddSROAPass => Capability::Passes,
+        add_type_based_alias_analysis_pass: LLVMPY_AddTypeBasedAliasAnalysisPass => Capability::Passes,
+        add_basic_alias_analysis_pass: LLVMPY_AddBasicAliasAnalysisPass => Capability::Passes,
+        llvm_add_loop_rotate_pass: LLVMPY_LLVMAddLoopRotatePass => Capability::Passes,
+        get_process_triple: LLVMPY_GetProcessTriple,
+        get_host_c_p_u_features: LLVMPY_GetHostCPUFeatures,
+        get_default_target_triple: LLVMPY_GetDefaultTargetTriple,
+        get_host_c_p_u_name: LLVMPY_GetHostCPUName,
+        get_triple_object_format: LLVMPY_GetTripleObjectFormat,
+        create_target_data: LLVMPY_CreateTargetData,
+        copy_string_rep_of_target_data: LLVMPY_CopyStringRepOfTargetData,
+        dispose_target_data: LLVMPY_DisposeTargetData,
+        abi_size_of_type: LLVMPY_ABISizeOfType,
+        offset_of_element: LLVMPY_OffsetOfElement,
+        abi_size_of_element_type: LLVMPY_ABISizeOfElementType,
+        abi_alignment_of_element_type: LLVMPY_ABIAlignmentOfElementType,
+        get_target_from_triple: LLVMPY_GetTargetFromTriple,
+        get_target_name: LLVMPY_GetTargetName,
+        get_target_description: LLVMPY_GetTargetDescription,
+        create_target_machine: LLVMPY_CreateTargetMachine,
+        dispose_target_machine: LLVMPY_DisposeTargetMachine,
+        get_target_machine_triple: LLVMPY_GetTargetMachineTriple,
+        set_target_machine_asm_verbosity: LLVMPY_SetTargetMachineAsmVerbosity,
+        target_machine_emit_to_memory: LLVMPY_TargetMachineEmitToMemory,
+        create_target_machine_data: LLVMPY_CreateTargetMachineData,
+        add_analysis_passes: LLVMPY_AddAnalysisPasses,
+        get_buffer_start: LLVMPY_GetBufferStart,
+        get_buffer_size: LLVMPY_GetBufferSize,
+        dispose_memory_buffer: LLVMPY_DisposeMemoryBuffer,
+        has_svml_support: LLVMPY_HasSVMLSupport => Capability::Svml,
+        pass_manager_builder_create: LLVMPY_PassManagerBuilderCreate,
+        pass_manager_builder_dispose: LLVMPY_PassManagerBuilderDispose,
+        pass_manager_builder_get_opt_level: LLVMPY_PassManagerBuilderGetOptLevel,
+        pass_manager_builder_set_opt_level: LLVMPY_PassManagerBuilderSetOptLevel,
+        pass_manager_builder_get_size_level: LLVMPY_PassManagerBuilderGetSizeLevel,
+        pass_manager_builder_set_size_level: LLVMPY_PassManagerBuilderSetSizeLevel,
+        pass_manager_builder_get_disable_unroll_loops: LLVMPY_PassManagerBuilderGetDisableUnrollLoops,
+        pass_manager_builder_set_disable_unroll_loops: LLVMPY_PassManagerBuilderSetDisableUnrollLoops,
+        pass_manager_builder_use_inliner_with_threshold: LLVMPY_PassManagerBuilderUseInlinerWithThreshold,
+        pass_manager_builder_populate_function_pass_manager: LLVMPY_PassManagerBuilderPopulateFunctionPassManager,
+        pass_manager_builder_set_loop_vectorize: LLVMPY_PassManagerBuilderSetLoopVectorize,
+        pass_manager_builder_get_loop_vectorize: LLVMPY_PassManagerBuilderGetLoopVectorize,
+        pass_manager_builder_set_s_l_p_vectorize: LLVMPY_PassManagerBuilderSetSLPVectorize,
+        pass_manager_builder_get_s_l_p_vectorize: LLVMPY_PassManagerBuilderGetSLPVectorize,
+        function_attributes_iter: LLVMPY_FunctionAttributesIter,
+        argument_attributes_iter: LLVMPY_ArgumentAttributesIter,
+        call_inst_attributes_iter: LLVMPY_CallInstAttributesIter,
+        invoke_inst_attributes_iter: LLVMPY_InvokeInstAttributesIter,
+        global_attributes_iter: LLVMPY_GlobalAttributesIter,
+        function_blocks_iter: LLVMPY_FunctionBlocksIter,
+        function_arguments_iter: LLVMPY_FunctionArgumentsIter,
+        block_instructions_iter: LLVMPY_BlockInstructionsIter,
+        instruction_operands_iter: LLVMPY_InstructionOperandsIter,
+        attribute_list_iter_next: LLVMPY_AttributeListIterNext,
+        attribute_set_iter_next: LLVMPY_AttributeSetIterNext,
+        blocks_iter_next: LLVMPY_BlocksIterNext,
+        arguments_iter_next: LLVMPY_ArgumentsIterNext,
+        instructions_iter_next: LLVMPY_InstructionsIterNext,
+        operands_iter_next: LLVMPY_OperandsIterNext,
+        dispose_attribute_list_iter: LLVMPY_DisposeAttributeListIter,
+        dispose_attribute_set_iter: LLVMPY_DisposeAttributeSetIter,
+        dispose_blocks_iter: LLVMPY_DisposeBlocksIter,
+        dispose_arguments_iter: LLVMPY_DisposeArgumentsIter,
+        dispose_instructions_iter: LLVMPY_DisposeInstructionsIter,
+        dispose_operands_iter: LLVMPY_DisposeOperandsIter,
+        print_value_to_string: LLVMPY_PrintValueToString,
+        get_value_name: LLVMPY_GetValueName,
+        get_value_kind: LLVMPY_GetValueKind,
+        set_value_name: LLVMPY_SetValueName,
+        get_global_parent: LLVMPY_GetGlobalParent,
+        type_of: LLVMPY_TypeOf,
+        print_type: LLVMPY_PrintType,
+        get_type_name: LLVMPY_GetTypeName,
+        type_is_pointer: LLVMPY_TypeIsPointer,
+        get_element_type: LLVMPY_GetElementType,
+        set_linkage: LLVMPY_SetLinkage,
+        get_linkage: LLVMPY_GetLinkage,
+        set_visibility: LLVMPY_SetVisibility,
+        get_visibility: LLVMPY_GetVisibility,
+        set_dll_storage_class: LLVMPY_SetDLLStorageClass,
+        get_dll_storage_class: LLVMPY_GetDLLStorageClass,
+        get_enum_attribute_kind_for_name: LLVMPY_GetEnumAttributeKindForName,
+        add_function_attr: LLVMPY_AddFunctionAttr,
+        is_declaration: LLVMPY_IsDeclaration,
+        write_cfg: LLVMPY_WriteCFG => Capability::DotPrinters,
+        get_opcode_name: LLVMPY_GetOpcodeName,
+    }
+}
+
+/// The symbol used to check the version of a library before anything else is loaded.
+const VERSION_SYMBOL: &str = "LLVMPY_GetVersionInfo";
 
-    lib.get(name)
-        .map_err(|e| LoadError::LoadFunction {
-            inner: e,
-            target: pretty_name.to_string(),
-        })
+/// Reads the version of LLVM the library was built against, rejecting it
+/// if it is not supported.
+unsafe fn check_version(
+    get_version_info: LLVMPY_GetVersionInfo,
+) -> Result<LlvmVersion, LoadError> {
+    let version = LlvmVersion::from_packed(get_version_info());
+    if !version.is_supported() {
+        return Err(LoadError::IncompatibleVersion { found: version });
+    }
+    Ok(version)
+}
+
+unsafe fn load_optional<T: Copy>(
+    lib: &Library,
+    name: &'static str,
+    capability: Capability,
+    unavailable: &mut Vec<Capability>,
+) -> OptionalSymbol<T> {
+    let inner = load_fn(lib, name);
+
+    if inner.is_none() && !unavailable.contains(&capability) {
+        unavailable.push(capability);
+    }
+
+    OptionalSymbol {
+        name,
+        capability,
+        inner,
+    }
+}
+
+unsafe fn load_fn<T: Copy>(lib: &Library, name: &str) -> Option<T> {
+    lib.get::<T>(name.as_bytes()).ok().map(|symbol| *symbol)
 }
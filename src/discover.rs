@@ -0,0 +1,107 @@
+use std::env;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+/// The environment variable checked first by [crate::LLVMLite::discover].
+///
+/// It may point either at the shared library itself or at the directory
+/// containing it.
+pub const LIBRARY_ENV_VAR: &str = "LLVMLITE_LIB";
+
+/// The bare name of the llvmlite shared library, without the platform
+/// specific prefix and extension.
+pub(crate) const LIBRARY_NAME: &str = "llvmlite";
+
+/// The platform specific file name of the shared library, i.e. `libllvmlite.so`.
+pub(crate) fn library_filename() -> OsString {
+    libloading::library_filename(LIBRARY_NAME)
+}
+
+/// Produces every path the library may live at, in the order they should be tried.
+///
+/// This covers the explicit environment override followed by the site-packages
+/// layouts used by virtualenvs, conda environments, user installs and
+/// system wide Python installs. The system loader search paths are not included
+/// as those are resolved by the OS itself.
+pub(crate) fn candidates() -> Vec<PathBuf> {
+    let filename = library_filename();
+    let mut candidates = Vec::new();
+
+    if let Some(path) = env::var_os(LIBRARY_ENV_VAR) {
+        let path = PathBuf::from(path);
+        if path.is_dir() {
+            candidates.push(path.join(&filename));
+        } else {
+            candidates.push(path);
+        }
+    }
+
+    for site_packages in site_packages_dirs() {
+        candidates.push(site_packages.join("llvmlite").join("binding").join(&filename));
+    }
+
+    candidates
+}
+
+/// Produces the Python package directories worth searching.
+fn site_packages_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+
+    if let Some(paths) = env::var_os("PYTHONPATH") {
+        dirs.extend(env::split_paths(&paths).filter(|p| !p.as_os_str().is_empty()));
+    }
+
+    let mut prefixes = Vec::new();
+    for var in ["VIRTUAL_ENV", "CONDA_PREFIX"] {
+        if let Some(prefix) = env::var_os(var) {
+            prefixes.push(PathBuf::from(prefix));
+        }
+    }
+
+    if cfg!(windows) {
+        if let Some(app_data) = env::var_os("APPDATA") {
+            let python = PathBuf::from(app_data).join("Python");
+            prefixes.extend(read_dir_matching(&python, "Python3"));
+        }
+    } else {
+        if let Some(home) = env::var_os("HOME") {
+            prefixes.push(PathBuf::from(home).join(".local"));
+        }
+        prefixes.push(PathBuf::from("/usr/local"));
+        prefixes.push(PathBuf::from("/usr"));
+    }
+
+    for prefix in prefixes {
+        if cfg!(windows) {
+            dirs.push(prefix.join("Lib").join("site-packages"));
+            continue;
+        }
+
+        for lib in ["lib", "lib64"] {
+            for python in read_dir_matching(&prefix.join(lib), "python3") {
+                dirs.push(python.join("site-packages"));
+                dirs.push(python.join("dist-packages"));
+            }
+        }
+        dirs.push(prefix.join("lib").join("python3").join("dist-packages"));
+    }
+
+    dirs
+}
+
+/// Lists the entries of `dir` whose names start with `prefix`, sorted so the
+/// search order is stable between runs.
+fn read_dir_matching(dir: &Path, prefix: &str) -> Vec<PathBuf> {
+    let Ok(entries) = dir.read_dir() else {
+        return Vec::new();
+    };
+
+    let mut matches: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with(prefix))
+        .map(|entry| entry.path())
+        .collect();
+
+    matches.sort();
+    matches
+}
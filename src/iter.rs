@@ -0,0 +1,73 @@
+use crate::module::Module;
+
+/// A native LLVM iterator, disposed when dropped.
+///
+/// LLVM iterators yield raw pointers until they return null. The iterator
+/// borrows the module it walks, so the module cannot be modified or dropped
+/// while it, or any handle it produced, is alive.
+///
+/// The one change made through a shared borrow is adding an attribute with
+/// [Function::add_attribute](crate::Function::add_attribute). That swaps the
+/// function's attribute list for a new one, which adds or removes no values
+/// and leaves names untouched, and attribute strings are always copied out
+/// rather than borrowed.
+pub(crate) struct RawIter<'m, I: Copy, P> {
+    inner: I,
+    next: unsafe extern "C" fn(I) -> *mut P,
+    dispose: unsafe extern "C" fn(I),
+    pub(crate) module: &'m Module<'m>,
+}
+
+impl<'m, I: Copy, P> RawIter<'m, I, P> {
+    /// Wraps a native iterator which is disposed with `dispose`.
+    pub(crate) unsafe fn new(
+        inner: I,
+        next: unsafe extern "C" fn(I) -> *mut P,
+        dispose: unsafe extern "C" fn(I),
+        module: &'m Module<'m>,
+    ) -> Self {
+        Self {
+            inner,
+            next,
+            dispose,
+            module,
+        }
+    }
+}
+
+impl<'m, I: Copy, P> Iterator for RawIter<'m, I, P> {
+    type Item = *mut P;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = unsafe { (self.next)(self.inner) };
+        (!item.is_null()).then_some(item)
+    }
+}
+
+impl<'m, I: Copy, P> Drop for RawIter<'m, I, P> {
+    fn drop(&mut self) {
+        unsafe { (self.dispose)(self.inner) }
+    }
+}
+
+/// Declares a public iterator wrapping a [RawIter], yielding a handle type
+/// built with its `from_raw` constructor.
+macro_rules! handle_iter {
+    ($(#[$attr:meta])* $name:ident, $iter:ty, $raw:ty => $item:ident) => {
+        $(#[$attr])*
+        pub struct $name<'m> {
+            pub(crate) raw: crate::iter::RawIter<'m, $iter, $raw>,
+        }
+
+        impl<'m> Iterator for $name<'m> {
+            type Item = $item<'m>;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                let inner = self.raw.next()?;
+                Some(unsafe { $item::from_raw(inner, self.raw.module) })
+            }
+        }
+    };
+}
+
+pub(crate) use handle_iter;
@@ -1,8 +1,44 @@
+mod attribute;
 mod binding;
+mod discover;
+mod iter;
+mod metadata;
+mod module;
+mod opcode;
+mod typeref;
+mod value;
+mod verify;
+mod version;
 mod wrappers;
 
 pub use wrappers::{
     ByteString,
+    Context,
+    LlvmBytes,
+    StringError,
     Utf8String,
 };
-pub use binding::{LLVMLite, LoadError};
\ No newline at end of file
+pub use attribute::{Attribute, AttributeError, AttributeSet};
+pub use metadata::MetadataError;
+pub use module::{IrParseError, LinkError, Module, ParseError};
+pub use opcode::Opcode;
+pub use binding::{Capability, LLVMLite, LoadError, MissingSymbol, SymbolStatus};
+pub use discover::LIBRARY_ENV_VAR;
+pub use llvmlite_types::{LLVMOpcode, LLVMValueKind};
+pub use typeref::{StructType, StructTypes};
+pub use value::{
+    Argument,
+    Arguments,
+    BasicBlock,
+    Blocks,
+    Function,
+    Functions,
+    GlobalVariable,
+    Globals,
+    Instruction,
+    Instructions,
+    Operands,
+    Value,
+};
+pub use verify::{VerifyEntry, VerifyError};
+pub use version::{LlvmVersion, MAX_LLVM_MAJOR, MIN_LLVM_MAJOR};
\ No newline at end of file
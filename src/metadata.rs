@@ -0,0 +1,251 @@
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+
+/// An error produced when a target triple or data layout is malformed.
+pub enum MetadataError {
+    InvalidTriple {
+        triple: String,
+        reason: &'static str,
+    },
+    InvalidDataLayout {
+        layout: String,
+        reason: String,
+    },
+}
+
+impl Display for MetadataError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MetadataError::InvalidTriple { triple, reason } => {
+                write!(f, "MetadataError(invalid_triple={triple:?}, reason={reason:?})")
+            },
+            MetadataError::InvalidDataLayout { layout, reason } => {
+                write!(f, "MetadataError(invalid_data_layout={layout:?}, reason={reason:?})")
+            },
+        }
+    }
+}
+
+impl Debug for MetadataError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self}")
+    }
+}
+
+impl Error for MetadataError {}
+
+/// Checks a target triple is made of `-` separated components, starting with
+/// the architecture.
+///
+/// LLVM itself accepts any string and silently treats unknown components as
+/// `unknown`, so this only rejects triples which cannot be meant seriously.
+/// Other components may be empty, as in `amdgcn--amdhsa`, and any number may
+/// follow, as in `x86_64-pc-windows-msvc-elf`. An empty triple is allowed and
+/// clears the target.
+pub(crate) fn validate_triple(triple: &str) -> Result<(), MetadataError> {
+    if triple.is_empty() {
+        return Ok(());
+    }
+
+    let invalid = |reason| MetadataError::InvalidTriple {
+        triple: triple.to_string(),
+        reason,
+    };
+
+    if triple.starts_with('-') {
+        return Err(invalid("the architecture must not be empty"));
+    }
+
+    let valid_char = |c: char| c.is_ascii_alphanumeric() || c == '_' || c == '.';
+    if !triple.split('-').all(|component| component.chars().all(valid_char)) {
+        return Err(invalid("components may only contain letters, digits, '_' and '.'"));
+    }
+
+    Ok(())
+}
+
+/// Checks every specification in a data layout string is well formed.
+///
+/// The layout is a `-` separated list of specifications, e.g. `e-m:e-i64:64-n32:64`,
+/// see <https://llvm.org/docs/LangRef.html#data-layout>. An empty layout is allowed
+/// and resets the module to the default layout.
+pub(crate) fn validate_data_layout(layout: &str) -> Result<(), MetadataError> {
+    if layout.is_empty() {
+        return Ok(());
+    }
+
+    for spec in layout.split('-') {
+        validate_spec(spec).map_err(|reason| MetadataError::InvalidDataLayout {
+            layout: layout.to_string(),
+            reason,
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Checks a single specification against the rules `DataLayout::parseSpecifier`
+/// enforces, as every error it reports aborts the process.
+///
+/// This is stricter than LLVM in places, e.g. trailing fields it would ignore
+/// are rejected.
+fn validate_spec(spec: &str) -> Result<(), String> {
+    let fail = |reason: &str| format!("{reason} in specification {spec:?}");
+
+    if spec.is_empty() {
+        return Err("empty specification".to_string());
+    }
+    if spec.starts_with(':') || spec.ends_with(':') || spec.contains("::") {
+        return Err(fail("empty field"));
+    }
+
+    let mut fields = spec.split(':');
+    let first = fields.next().unwrap_or_default();
+    let fields: Vec<&str> = fields.collect();
+    let field = |index: usize| fields.get(index).copied();
+    let required = |index, missing: &str| field(index).ok_or_else(|| fail(missing));
+    let at_most = |count: usize| match fields.len() > count {
+        true => Err(fail("too many fields")),
+        false => Ok(()),
+    };
+
+    let check = |result: Result<u32, &str>| result.map_err(fail);
+
+    // Non-integral address spaces, e.g. `ni:1:2`.
+    if first == "ni" {
+        for space in &fields {
+            if check(address_space(space))? == 0 {
+                return Err(fail("address space 0 cannot be non-integral"));
+            }
+        }
+        return Ok(());
+    }
+
+    let mut chars = first.chars();
+    let kind = chars.next().unwrap_or_default();
+    let rest = chars.as_str();
+
+    match kind {
+        'e' | 'E' if rest.is_empty() => at_most(0),
+        'p' => {
+            if !rest.is_empty() {
+                check(address_space(rest))?;
+            }
+            let size = check(int(required(0, "missing pointer size")?))?;
+            if size == 0 {
+                return Err(fail("pointer size must not be 0"));
+            }
+            let abi = check(alignment(required(1, "missing pointer alignment")?))?;
+            if !abi.is_power_of_two() {
+                return Err(fail("pointer ABI alignment must be a power of 2"));
+            }
+            if let Some(pref) = field(2) {
+                let pref = check(alignment(pref))?;
+                if !pref.is_power_of_two() {
+                    return Err(fail("pointer preferred alignment must be a power of 2"));
+                }
+                if pref < abi {
+                    return Err(fail("preferred alignment is below the ABI alignment"));
+                }
+            }
+            if let Some(index) = field(3) {
+                let index = check(int(index))?;
+                if index == 0 || index > size {
+                    return Err(fail("index size must be 1 up to the pointer size"));
+                }
+            }
+            at_most(4)
+        },
+        'i' | 'v' | 'f' | 'a' => {
+            let size = if rest.is_empty() { 0 } else { check(int(rest))? };
+            if kind == 'a' && size != 0 {
+                return Err(fail("aggregate alignment must not have a size"));
+            }
+            if size >= 1 << 24 {
+                return Err(fail("bit width must fit in 24 bits"));
+            }
+            let abi = check(alignment(required(0, "missing alignment")?))?;
+            if kind != 'a' && abi == 0 {
+                return Err(fail("ABI alignment must not be 0"));
+            }
+            if kind == 'i' && size == 8 && abi != 8 {
+                return Err(fail("i8 must be naturally aligned"));
+            }
+            let pref = match field(1) {
+                Some(pref) => check(alignment(pref))?,
+                None => abi,
+            };
+            for align in [abi, pref] {
+                if align != 0 && !align.is_power_of_two() {
+                    return Err(fail("alignment must be 0 or a power of 2"));
+                }
+                if align / 8 >= 1 << 16 {
+                    return Err(fail("alignment must fit in 16 bits"));
+                }
+            }
+            if pref < abi {
+                return Err(fail("preferred alignment is below the ABI alignment"));
+            }
+            at_most(2)
+        },
+        'n' => {
+            for width in std::iter::once(rest).chain(fields.iter().copied()) {
+                if check(int(width))? == 0 {
+                    return Err(fail("native integer width must not be 0"));
+                }
+            }
+            Ok(())
+        },
+        'S' => {
+            let align = check(alignment(rest))?;
+            if align != 0 && !align.is_power_of_two() {
+                return Err(fail("stack alignment must be 0 or a power of 2"));
+            }
+            at_most(0)
+        },
+        'F' => {
+            let Some(align) = rest.strip_prefix(['i', 'n']) else {
+                return Err(fail("function pointer alignment type must be 'i' or 'n'"));
+            };
+            let align = check(alignment(align))?;
+            if align != 0 && !align.is_power_of_two() {
+                return Err(fail("function pointer alignment must be 0 or a power of 2"));
+            }
+            at_most(0)
+        },
+        'P' | 'A' | 'G' => {
+            check(address_space(rest))?;
+            at_most(0)
+        },
+        'm' if rest.is_empty() => match fields.as_slice() {
+            ["e" | "l" | "m" | "o" | "w" | "x" | "a"] => Ok(()),
+            _ => Err(fail("unknown mangling style")),
+        },
+        _ => Err(format!("unknown specification {spec:?}")),
+    }
+}
+
+/// Parses a number field, which LLVM reads as an unsigned int.
+fn int(field: &str) -> Result<u32, &'static str> {
+    if field.is_empty() || !field.bytes().all(|b| b.is_ascii_digit()) {
+        return Err("expected a number");
+    }
+    field.parse().map_err(|_| "number does not fit in 32 bits")
+}
+
+/// Parses an alignment in bits, which must be a whole number of bytes.
+fn alignment(field: &str) -> Result<u32, &'static str> {
+    let bits = int(field)?;
+    if bits % 8 != 0 {
+        return Err("alignment must be a multiple of 8 bits");
+    }
+    Ok(bits)
+}
+
+fn address_space(field: &str) -> Result<u32, &'static str> {
+    let space = int(field)?;
+    if space >= 1 << 24 {
+        return Err("address space must fit in 24 bits");
+    }
+    Ok(space)
+}
@@ -0,0 +1,460 @@
+use std::borrow::Cow;
+use std::error::Error;
+use std::ffi::{c_char, CStr, CString};
+use std::fmt::{Debug, Display, Formatter};
+use std::io;
+use std::mem;
+use std::ptr;
+use std::sync::Arc;
+
+use llvmlite_types::LLVMModuleRef;
+
+use crate::binding::LLVMMethods;
+use crate::iter::RawIter;
+use crate::metadata::{self, MetadataError};
+use crate::typeref::{StructType, StructTypes};
+use crate::value::{Function, Functions, GlobalVariable, Globals};
+use crate::verify::VerifyError;
+use crate::wrappers::{Context, LlvmBytes, StringError};
+
+/// An error produced when LLVM fails to parse a module from bitcode.
+pub struct ParseError {
+    /// The error message reported by LLVM.
+    pub message: String,
+}
+
+impl ParseError {
+    /// Takes ownership of an LLVM allocated error message, disposing it
+    /// once it has been copied.
+    pub(crate) unsafe fn from_llvm(lib: &LLVMMethods, ptr: *const c_char) -> Self {
+        if ptr.is_null() {
+            return Self {
+                message: "LLVM did not provide an error message".to_string(),
+            };
+        }
+
+        Self {
+            message: take_string(lib, ptr),
+        }
+    }
+}
+
+/// An error produced when LLVM fails to parse textual IR, located in the source.
+///
+/// `Display` shows the offending line of IR with a caret under the column,
+/// `Debug` shows the fields.
+pub struct IrParseError {
+    /// The 1-based line of the error, or 0 if LLVM did not report a location.
+    pub line: usize,
+    /// The 1-based column of the error in bytes, or 0 if LLVM did not report a
+    /// location.
+    pub column: usize,
+    /// The error message reported by LLVM, without the location.
+    pub message: String,
+    source_line: Option<String>,
+}
+
+impl IrParseError {
+    /// Parses a diagnostic of the form `<string>:12:5: error: message`.
+    ///
+    /// LLVM follows the first line with its own copy of the source line and
+    /// a caret, those are rebuilt from `ir` instead.
+    pub(crate) fn from_diagnostic(diagnostic: &str, ir: &str) -> Self {
+        let first = diagnostic.lines().next().unwrap_or_default();
+
+        let located = first.split_once(": error: ").and_then(|(location, message)| {
+            let mut parts = location.rsplitn(3, ':');
+            let column = parts.next()?.parse().ok()?;
+            let line = parts.next()?.parse().ok()?;
+            Some((line, column, message))
+        });
+
+        match located {
+            Some((line, column, message)) => Self::at(ir, line, column, message.to_string()),
+            None => Self {
+                line: 0,
+                column: 0,
+                message: first.trim().to_string(),
+                source_line: None,
+            },
+        }
+    }
+
+    /// Creates an error pointing at a byte offset in `ir`.
+    pub(crate) fn at_offset(ir: &str, offset: usize, message: String) -> Self {
+        let before = &ir.as_bytes()[..offset.min(ir.len())];
+        let line = before.iter().filter(|b| **b == b'\n').count() + 1;
+        let line_start = before.iter().rposition(|b| *b == b'\n').map_or(0, |i| i + 1);
+        Self::at(ir, line, offset - line_start + 1, message)
+    }
+
+    fn at(ir: &str, line: usize, column: usize, message: String) -> Self {
+        let source_line = line
+            .checked_sub(1)
+            .and_then(|index| ir.lines().nth(index))
+            .map(|source| source.to_string());
+
+        Self {
+            line,
+            column,
+            message,
+            source_line,
+        }
+    }
+
+    /// Returns the line of IR the error points at, if known.
+    pub fn source_line(&self) -> Option<&str> {
+        self.source_line.as_deref()
+    }
+}
+
+impl Display for IrParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.line == 0 {
+            return write!(f, "error: {}", self.message);
+        }
+
+        write!(f, "{}:{}: error: {}", self.line, self.column, self.message)?;
+        if let Some(source) = &self.source_line {
+            // The column counts bytes, but the caret is indented by characters.
+            let mut end = self.column.saturating_sub(1).min(source.len());
+            while !source.is_char_boundary(end) {
+                end -= 1;
+            }
+            // Keep tabs so the caret lines up however the terminal renders them.
+            let indent: String = source[..end]
+                .chars()
+                .map(|c| if c == '\t' { '\t' } else { ' ' })
+                .collect();
+            write!(f, "\n{source}\n{indent}^")?;
+        }
+        Ok(())
+    }
+}
+
+impl Debug for IrParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "IrParseError(line={}, column={}, message={:?})",
+            self.line, self.column, self.message,
+        )
+    }
+}
+
+impl Error for IrParseError {}
+
+/// An error produced when LLVM fails to link two modules.
+pub struct LinkError {
+    /// The error message reported by LLVM, such as a duplicate symbol.
+    pub message: String,
+}
+
+impl Display for LinkError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "LinkError(message={:?})", self.message)
+    }
+}
+
+impl Debug for LinkError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self}")
+    }
+}
+
+impl Error for LinkError {}
+
+/// Views a string owned by LLVM, which must outlive the returned value.
+///
+/// A null pointer is treated as an empty string.
+pub(crate) unsafe fn borrow_string<'a>(ptr: *const c_char) -> Cow<'a, str> {
+    if ptr.is_null() {
+        return Cow::Borrowed("");
+    }
+
+    CStr::from_ptr(ptr).to_string_lossy()
+}
+
+/// Copies an LLVM allocated string, disposing it afterwards.
+///
+/// A null pointer is treated as an empty string.
+pub(crate) unsafe fn take_string(lib: &LLVMMethods, ptr: *const c_char) -> String {
+    if ptr.is_null() {
+        return String::new();
+    }
+
+    let s = CStr::from_ptr(ptr).to_string_lossy().into_owned();
+    (lib.dispose_string)(ptr);
+    s
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ParseError(message={:?})", self.message)
+    }
+}
+
+impl Debug for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self}")
+    }
+}
+
+impl Error for ParseError {}
+
+/// A safe wrapper around a LLVM module.
+///
+/// The module is owned by the [Context] it was created in and is disposed
+/// when the value is dropped.
+pub struct Module<'ctx> {
+    pub(crate) inner: LLVMModuleRef,
+    pub(crate) ctx: &'ctx Context,
+}
+
+impl<'ctx> Module<'ctx> {
+    /// Returns the context this module belongs to.
+    pub fn context(&self) -> &'ctx Context {
+        self.ctx
+    }
+
+    /// Returns the module identifier.
+    ///
+    /// The name is borrowed from the module unless it is not valid UTF-8, in
+    /// which case invalid sequences are replaced. The same applies to the other
+    /// metadata getters.
+    pub fn name(&self) -> Cow<'_, str> {
+        unsafe { borrow_string((self.ctx.lib.get_module_name)(self.inner)) }
+    }
+
+    /// Sets the module identifier.
+    pub fn set_name(&mut self, name: &str) -> Result<(), StringError> {
+        let name = CString::new(name)?;
+        unsafe { (self.ctx.lib.set_module_name)(self.inner, name.as_ptr()) };
+        Ok(())
+    }
+
+    /// Returns the name of the source file the module was created from.
+    pub fn source_file_name(&self) -> Cow<'_, str> {
+        unsafe { borrow_string((self.ctx.lib.get_module_source_file_name)(self.inner)) }
+    }
+
+    /// Returns the target triple, e.g. `x86_64-unknown-linux-gnu`.
+    ///
+    /// This is empty if the module has no target.
+    pub fn triple(&self) -> Cow<'_, str> {
+        let mut triple: *const c_char = ptr::null();
+        unsafe {
+            (self.ctx.lib.get_target)(self.inner, &mut triple);
+            borrow_string(triple)
+        }
+    }
+
+    /// Sets the target triple, an empty triple clears it.
+    ///
+    /// The triple must start with an architecture, e.g. `x86_64-pc-linux-gnu`,
+    /// the other components may be empty as in `nvptx64--`.
+    pub fn set_triple(&mut self, triple: &str) -> Result<(), MetadataError> {
+        metadata::validate_triple(triple)?;
+
+        // Validation rejects NUL bytes.
+        let triple = CString::new(triple).expect("triple was validated");
+        unsafe { (self.ctx.lib.set_target)(self.inner, triple.as_ptr()) };
+        Ok(())
+    }
+
+    /// Returns the data layout string, e.g. `e-m:e-i64:64-n32:64-S128`.
+    pub fn data_layout(&self) -> Cow<'_, str> {
+        let mut layout: *const c_char = ptr::null();
+        unsafe {
+            (self.ctx.lib.get_data_layout)(self.inner, &mut layout);
+            borrow_string(layout)
+        }
+    }
+
+    /// Sets the data layout, an empty layout resets it to the default.
+    ///
+    /// The layout is checked against the rules LLVM enforces, such as sizes and
+    /// alignments being present and alignments being powers of two in whole
+    /// bytes, as LLVM aborts the process when given a malformed layout.
+    pub fn set_data_layout(&mut self, layout: &str) -> Result<(), MetadataError> {
+        metadata::validate_data_layout(layout)?;
+
+        // Validation rejects NUL bytes.
+        let layout = CString::new(layout).expect("data layout was validated");
+        unsafe { (self.ctx.lib.set_data_layout)(self.inner, layout.as_ptr()) };
+        Ok(())
+    }
+
+    /// Returns an iterator over the functions defined or declared in the module.
+    pub fn functions(&self) -> Functions<'_> {
+        let lib = &self.ctx.lib;
+        let raw = unsafe {
+            RawIter::new(
+                (lib.module_functions_iter)(self.inner),
+                lib.functions_iter_next,
+                lib.dispose_functions_iter,
+                self,
+            )
+        };
+        Functions { raw }
+    }
+
+    /// Returns an iterator over the global variables in the module.
+    pub fn globals(&self) -> Globals<'_> {
+        let lib = &self.ctx.lib;
+        let raw = unsafe {
+            RawIter::new(
+                (lib.module_globals_iter)(self.inner),
+                lib.globals_iter_next,
+                lib.dispose_globals_iter,
+                self,
+            )
+        };
+        Globals { raw }
+    }
+
+    /// Returns an iterator over the named struct types used by the module.
+    pub fn struct_types(&self) -> StructTypes<'_> {
+        let lib = &self.ctx.lib;
+        let raw = unsafe {
+            RawIter::new(
+                (lib.module_types_iter)(self.inner),
+                lib.types_iter_next,
+                lib.dispose_types_iter,
+                self,
+            )
+        };
+        StructTypes { raw }
+    }
+
+    /// Looks up a function by name, returning `None` if there is no such function.
+    pub fn get_function(&self, name: &str) -> Result<Option<Function<'_>>, StringError> {
+        let name = CString::new(name)?;
+        let inner = unsafe { (self.ctx.lib.get_named_function)(self.inner, name.as_ptr()) };
+
+        if inner.is_null() {
+            return Ok(None);
+        }
+        Ok(Some(unsafe { Function::from_raw(inner, self) }))
+    }
+
+    /// Looks up a global variable by name, returning `None` if there is no such
+    /// global.
+    pub fn get_global(
+        &self,
+        name: &str,
+    ) -> Result<Option<GlobalVariable<'_>>, StringError> {
+        let name = CString::new(name)?;
+        let inner =
+            unsafe { (self.ctx.lib.get_named_global_variable)(self.inner, name.as_ptr()) };
+
+        if inner.is_null() {
+            return Ok(None);
+        }
+        Ok(Some(unsafe { GlobalVariable::from_raw(inner, self) }))
+    }
+
+    /// Looks up a named struct type, returning `None` if there is no such type.
+    pub fn get_struct_type(
+        &self,
+        name: &str,
+    ) -> Result<Option<StructType<'_>>, StringError> {
+        let name = CString::new(name)?;
+        let inner =
+            unsafe { (self.ctx.lib.get_named_struct_type)(self.inner, name.as_ptr()) };
+
+        if inner.is_null() {
+            return Ok(None);
+        }
+        Ok(Some(unsafe { StructType::from_raw(inner, self) }))
+    }
+
+    /// Links `other` into this module.
+    ///
+    /// LLVM destroys the source module whether or not linking succeeds, so it is
+    /// consumed. Both modules must belong to the same [Context]. If linking fails
+    /// this module may have been partially modified.
+    pub fn link_in(&mut self, other: Module<'ctx>) -> Result<(), LinkError> {
+        if !ptr::eq(self.ctx, other.ctx) {
+            return Err(LinkError {
+                message: "cannot link modules from different contexts".to_string(),
+            });
+        }
+
+        let mut err: *const c_char = ptr::null();
+        let failed = unsafe { (self.ctx.lib.link_modules)(self.inner, other.inner, &mut err) };
+        // The source module now belongs to LLVM and must not be disposed again.
+        mem::forget(other);
+
+        let message = unsafe { take_string(&self.ctx.lib, err) };
+        if failed != 0 {
+            return Err(LinkError { message });
+        }
+
+        Ok(())
+    }
+
+    /// Checks the module is well formed.
+    ///
+    /// Each problem the verifier finds is returned as a separate entry, along
+    /// with the function it was found in where that can be determined.
+    pub fn verify(&self) -> Result<(), VerifyError> {
+        let mut message: *const c_char = ptr::null();
+        let broken = unsafe { (self.ctx.lib.verify_module)(self.inner, &mut message) };
+        let raw = unsafe { take_string(&self.ctx.lib, message) };
+
+        if broken == 0 {
+            return Ok(());
+        }
+
+        Err(VerifyError::parse(raw, &self.print_ir()))
+    }
+
+    /// Prints the module as textual LLVM IR.
+    pub(crate) fn print_ir(&self) -> String {
+        let mut ir: *const c_char = ptr::null();
+        unsafe {
+            (self.ctx.lib.print_module_to_string)(self.inner, &mut ir);
+            take_string(&self.ctx.lib, ir)
+        }
+    }
+
+    /// Serializes the module to LLVM bitcode.
+    ///
+    /// The buffer is owned by LLVM, use `to_vec` if it needs to outlive
+    /// the library or be modified.
+    pub fn to_bitcode(&self) -> LlvmBytes {
+        let mut ptr: *const c_char = ptr::null();
+        let mut len = 0;
+        unsafe {
+            (self.ctx.lib.write_bitcode_to_string)(self.inner, &mut ptr, &mut len);
+            LlvmBytes::from_raw(Arc::clone(&self.ctx.lib), ptr, len)
+        }
+    }
+
+    /// Serializes the module to LLVM bitcode and writes it to `writer`.
+    pub fn write_bitcode(&self, mut writer: impl io::Write) -> io::Result<()> {
+        writer.write_all(&self.to_bitcode())
+    }
+}
+
+/// Deep copies the module into a new module in the same [Context].
+///
+/// The copy is independent of the original and disposed separately.
+impl<'ctx> Clone for Module<'ctx> {
+    fn clone(&self) -> Self {
+        let inner = unsafe { (self.ctx.lib.clone_module)(self.inner) };
+        Self {
+            inner,
+            ctx: self.ctx,
+        }
+    }
+}
+
+impl<'ctx> Drop for Module<'ctx> {
+    fn drop(&mut self) {
+        if !self.inner.is_null() {
+            unsafe { (self.ctx.lib.dispose_module)(self.inner) }
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use std::convert::Infallible;
+use std::str::FromStr;
+
+use llvmlite_types::LLVMOpcode;
+
+/// The opcode of an [Instruction](crate::Instruction).
+///
+/// Opcodes are reported by name, names this crate does not know, such as ones
+/// added by a newer LLVM, are kept as [Opcode::Unknown] rather than rejected.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Opcode {
+    Known(LLVMOpcode),
+    Unknown(String),
+}
+
+impl Opcode {
+    /// Returns the opcode if it is one this crate knows.
+    pub fn known(&self) -> Option<LLVMOpcode> {
+        match self {
+            Opcode::Known(opcode) => Some(*opcode),
+            Opcode::Unknown(_) => None,
+        }
+    }
+
+    /// Returns if the opcode ends a basic block, such as `ret` or `br`.
+    pub fn is_terminator(&self) -> bool {
+        use LLVMOpcode::*;
+
+        matches!(
+            self.known(),
+            Some(
+                LLVMRet
+                    | LLVMBr
+                    | LLVMSwitch
+                    | LLVMIndirectBr
+                    | LLVMInvoke
+                    | LLVMResume
+                    | LLVMUnreachable
+                    | LLVMCleanupRet
+                    | LLVMCatchRet
+                    | LLVMCatchSwitch
+                    | LLVMCallBr
+            )
+        )
+    }
+
+    /// Returns if the opcode calls a function, which includes `invoke` and
+    /// `callbr` as well as `call`.
+    pub fn is_call(&self) -> bool {
+        use LLVMOpcode::*;
+
+        matches!(self.known(), Some(LLVMCall | LLVMInvoke | LLVMCallBr))
+    }
+
+    /// Returns if the opcode reads or writes memory through a pointer operand,
+    /// such as `load`, `store` and the atomic operations.
+    ///
+    /// Calls are not included even though the callee may access memory.
+    pub fn is_memory_access(&self) -> bool {
+        use LLVMOpcode::*;
+
+        matches!(
+            self.known(),
+            Some(LLVMLoad | LLVMStore | LLVMAtomicCmpXchg | LLVMAtomicRMW | LLVMVAArg)
+        )
+    }
+}
+
+impl FromStr for Opcode {
+    type Err = Infallible;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Ok(match LLVMOpcode::from_name(name) {
+            Some(opcode) => Opcode::Known(opcode),
+            None => Opcode::Unknown(name.to_owned()),
+        })
+    }
+}
+
+impl From<LLVMOpcode> for Opcode {
+    fn from(opcode: LLVMOpcode) -> Self {
+        Opcode::Known(opcode)
+    }
+}
+
+impl PartialEq<LLVMOpcode> for Opcode {
+    fn eq(&self, other: &LLVMOpcode) -> bool {
+        self.known() == Some(*other)
+    }
+}
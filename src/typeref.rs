@@ -0,0 +1,40 @@
+use std::fmt::{Debug, Formatter};
+
+use llvmlite_types::{LLVMType, LLVMTypeRef, LLVMTypesIteratorRef};
+
+use crate::iter::handle_iter;
+use crate::module::{take_string, Module};
+
+/// A named struct type in a [Module].
+///
+/// The handle borrows the module, whose context owns the type.
+#[derive(Clone, Copy)]
+pub struct StructType<'m> {
+    pub(crate) inner: LLVMTypeRef,
+    pub(crate) module: &'m Module<'m>,
+}
+
+impl<'m> StructType<'m> {
+    pub(crate) unsafe fn from_raw(inner: LLVMTypeRef, module: &'m Module<'m>) -> Self {
+        Self { inner, module }
+    }
+
+    /// Returns the name of the struct, without the leading `%`.
+    pub fn name(&self) -> String {
+        unsafe {
+            let name = (self.module.ctx.lib.get_type_name)(self.inner);
+            take_string(&self.module.ctx.lib, name)
+        }
+    }
+}
+
+impl<'m> Debug for StructType<'m> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "StructType(name={:?})", self.name())
+    }
+}
+
+handle_iter! {
+    /// An iterator over the named struct types in a module, see [Module::struct_types].
+    StructTypes, LLVMTypesIteratorRef, LLVMType => StructType
+}
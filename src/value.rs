@@ -0,0 +1,390 @@
+use std::borrow::Cow;
+use std::fmt::{Debug, Formatter};
+use std::ops::Deref;
+
+use llvmlite_types::{
+    LLVMArgumentsIteratorRef,
+    LLVMBlocksIteratorRef,
+    LLVMFunctionsIteratorRef,
+    LLVMGlobalsIteratorRef,
+    LLVMInstructionsIteratorRef,
+    LLVMOpcode,
+    LLVMOperandsIteratorRef,
+    LLVMValue,
+    LLVMValueKind,
+    LLVMValueRef,
+};
+
+use crate::attribute::{Attribute, AttributeError, AttributeSet};
+use crate::iter::{handle_iter, RawIter};
+use crate::module::{borrow_string, take_string, Module};
+use crate::opcode::Opcode;
+
+/// A value in a [Module], such as a function, instruction or constant.
+///
+/// Values are owned by their module and borrow it, so they cannot outlive it
+/// or be used while it is being modified. The role a value plays is checked with
+/// the `as_*` downcasts, e.g. [Value::as_function], and each role derefs back
+/// to its `Value` so the common operations are available on all of them.
+#[derive(Clone, Copy)]
+pub struct Value<'m> {
+    pub(crate) inner: LLVMValueRef,
+    pub(crate) module: &'m Module<'m>,
+}
+
+impl<'m> Value<'m> {
+    pub(crate) unsafe fn from_raw(inner: LLVMValueRef, module: &'m Module<'m>) -> Self {
+        Self { inner, module }
+    }
+
+    /// Returns what kind of value this is.
+    ///
+    /// This is `None` if the library reports a kind newer than this crate knows.
+    pub fn kind(&self) -> Option<LLVMValueKind> {
+        let raw = unsafe { (self.module.ctx.lib.get_value_kind)(self.inner) };
+        LLVMValueKind::from_raw(raw)
+    }
+
+    /// Returns the name of the value without its `@` or `%` sigil.
+    ///
+    /// Unnamed values, such as most constants, have an empty name.
+    pub fn name(&self) -> Cow<'m, str> {
+        unsafe { borrow_string((self.module.ctx.lib.get_value_name)(self.inner)) }
+    }
+
+    /// Returns the module the value belongs to.
+    pub fn module(&self) -> &'m Module<'m> {
+        self.module
+    }
+
+    /// Returns the value as a [Function] if it is one.
+    pub fn as_function(&self) -> Option<Function<'m>> {
+        self.is(LLVMValueKind::LLVMFunctionValueKind)
+            .then_some(Function { value: *self })
+    }
+
+    /// Returns the value as a [GlobalVariable] if it is one.
+    pub fn as_global_variable(&self) -> Option<GlobalVariable<'m>> {
+        self.is(LLVMValueKind::LLVMGlobalVariableValueKind)
+            .then_some(GlobalVariable { value: *self })
+    }
+
+    /// Returns the value as a [BasicBlock] if it is one.
+    pub fn as_basic_block(&self) -> Option<BasicBlock<'m>> {
+        self.is(LLVMValueKind::LLVMBasicBlockValueKind)
+            .then_some(BasicBlock { value: *self })
+    }
+
+    /// Returns the value as an [Instruction] if it is one.
+    pub fn as_instruction(&self) -> Option<Instruction<'m>> {
+        self.is(LLVMValueKind::LLVMInstructionValueKind)
+            .then_some(Instruction { value: *self })
+    }
+
+    /// Returns the value as a function [Argument] if it is one.
+    pub fn as_argument(&self) -> Option<Argument<'m>> {
+        self.is(LLVMValueKind::LLVMArgumentValueKind)
+            .then_some(Argument { value: *self })
+    }
+
+    fn is(&self, kind: LLVMValueKind) -> bool {
+        self.kind() == Some(kind)
+    }
+}
+
+impl<'m> Debug for Value<'m> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Value(kind={:?}, name={:?})", self.kind(), self.name())
+    }
+}
+
+/// Declares a role a [Value] can play, which derefs to the value.
+macro_rules! value_role {
+    ($(#[$attr:meta])* $name:ident) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy)]
+        pub struct $name<'m> {
+            value: Value<'m>,
+        }
+
+        impl<'m> $name<'m> {
+            /// Wraps a raw value, which must play this role.
+            pub(crate) unsafe fn from_raw(inner: LLVMValueRef, module: &'m Module<'m>) -> Self {
+                Self {
+                    value: Value::from_raw(inner, module),
+                }
+            }
+        }
+
+        impl<'m> Deref for $name<'m> {
+            type Target = Value<'m>;
+
+            fn deref(&self) -> &Self::Target {
+                &self.value
+            }
+        }
+
+        impl<'m> From<$name<'m>> for Value<'m> {
+            fn from(role: $name<'m>) -> Self {
+                role.value
+            }
+        }
+
+        impl<'m> Debug for $name<'m> {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}(name={:?})", stringify!($name), self.name())
+            }
+        }
+    };
+}
+
+value_role! {
+    /// A function defined or declared in a [Module].
+    Function
+}
+
+value_role! {
+    /// A global variable in a [Module].
+    GlobalVariable
+}
+
+value_role! {
+    /// A basic block in the body of a [Function].
+    BasicBlock
+}
+
+value_role! {
+    /// An instruction in a [BasicBlock].
+    Instruction
+}
+
+value_role! {
+    /// A parameter of a [Function].
+    Argument
+}
+
+impl<'m> Function<'m> {
+    /// Returns if the function is only declared, having no body in this module.
+    pub fn is_declaration(&self) -> bool {
+        unsafe { (self.module.ctx.lib.is_declaration)(self.inner) != 0 }
+    }
+
+    /// Returns the attributes of the function itself, such as `nounwind`.
+    ///
+    /// See [Function::return_attributes] for those of the return value and
+    /// [Argument::attributes] for those of a parameter.
+    pub fn attributes(&self) -> AttributeSet {
+        self.attribute_set(0)
+    }
+
+    /// Returns the attributes of the return value, such as `noundef`.
+    pub fn return_attributes(&self) -> AttributeSet {
+        self.attribute_set(1)
+    }
+
+    fn attribute_set(&self, index: usize) -> AttributeSet {
+        let lib = &self.module.ctx.lib;
+        unsafe {
+            AttributeSet::collect_nth(
+                lib,
+                (lib.function_attributes_iter)(self.inner),
+                lib.attribute_list_iter_next,
+                lib.dispose_attribute_list_iter,
+                index,
+            )
+        }
+    }
+
+    /// Adds an enum attribute, such as `alwaysinline` or `cold`, to the function.
+    ///
+    /// The attribute can be given by name or as an [Attribute]. Unlike other
+    /// changes to the module this only needs a shared borrow, so attributes can
+    /// be added while walking [Module::functions]: the function's attribute list
+    /// is replaced, which invalidates nothing a handle or iterator refers to.
+    pub fn add_attribute(&self, attribute: impl Into<Attribute>) -> Result<(), AttributeError> {
+        let attribute = attribute.into();
+        let name = attribute.kind_name()?;
+
+        let lib = &self.module.ctx.lib;
+        let kind = unsafe {
+            (lib.get_enum_attribute_kind_for_name)(name.as_ptr().cast(), name.len())
+        };
+        if kind == 0 {
+            return Err(AttributeError::UnknownAttribute { name: name.to_owned() });
+        }
+
+        unsafe { (lib.add_function_attr)(self.inner, kind) };
+        Ok(())
+    }
+
+    /// Returns an iterator over the basic blocks in the body of the function.
+    ///
+    /// A declaration has no blocks.
+    pub fn blocks(&self) -> Blocks<'m> {
+        let lib = &self.module.ctx.lib;
+        let raw = unsafe {
+            RawIter::new(
+                (lib.function_blocks_iter)(self.inner),
+                lib.blocks_iter_next,
+                lib.dispose_blocks_iter,
+                self.module,
+            )
+        };
+        Blocks { raw }
+    }
+
+    /// Returns an iterator over the parameters of the function.
+    pub fn arguments(&self) -> Arguments<'m> {
+        let lib = &self.module.ctx.lib;
+        let raw = unsafe {
+            RawIter::new(
+                (lib.function_arguments_iter)(self.inner),
+                lib.arguments_iter_next,
+                lib.dispose_arguments_iter,
+                self.module,
+            )
+        };
+        Arguments { raw }
+    }
+}
+
+impl<'m> BasicBlock<'m> {
+    /// Returns an iterator over the instructions in the block, in order.
+    pub fn instructions(&self) -> Instructions<'m> {
+        let lib = &self.module.ctx.lib;
+        let raw = unsafe {
+            RawIter::new(
+                (lib.block_instructions_iter)(self.inner),
+                lib.instructions_iter_next,
+                lib.dispose_instructions_iter,
+                self.module,
+            )
+        };
+        Instructions { raw }
+    }
+}
+
+impl<'m> Instruction<'m> {
+    /// Returns the opcode of the instruction.
+    pub fn opcode(&self) -> Opcode {
+        let lib = &self.module.ctx.lib;
+        let name = unsafe { take_string(lib, (lib.get_opcode_name)(self.inner)) };
+        name.parse().unwrap_or_else(|never| match never {})
+    }
+
+    /// Returns the function attributes of a call site, such as `cold`, or `None`
+    /// if the instruction is not a `call` or `invoke`.
+    ///
+    /// Like [Function::attributes], these exclude the attributes of the return
+    /// value and of the arguments.
+    pub fn attributes(&self) -> Option<AttributeSet> {
+        self.attribute_set(0)
+    }
+
+    /// Returns the attributes of a call site's return value, or `None` if the
+    /// instruction is not a `call` or `invoke`.
+    pub fn return_attributes(&self) -> Option<AttributeSet> {
+        self.attribute_set(1)
+    }
+
+    fn attribute_set(&self, index: usize) -> Option<AttributeSet> {
+        let lib = &self.module.ctx.lib;
+        let iter = match self.opcode().known()? {
+            LLVMOpcode::LLVMCall => lib.call_inst_attributes_iter,
+            LLVMOpcode::LLVMInvoke => lib.invoke_inst_attributes_iter,
+            _ => return None,
+        };
+        let set = unsafe {
+            AttributeSet::collect_nth(
+                lib,
+                iter(self.inner),
+                lib.attribute_list_iter_next,
+                lib.dispose_attribute_list_iter,
+                index,
+            )
+        };
+        Some(set)
+    }
+
+    /// Returns an iterator over the operands of the instruction.
+    ///
+    /// Operands can be any kind of value, so they are yielded as [Value]s. The
+    /// callee of a call is its last operand.
+    pub fn operands(&self) -> Operands<'m> {
+        let lib = &self.module.ctx.lib;
+        let raw = unsafe {
+            RawIter::new(
+                (lib.instruction_operands_iter)(self.inner),
+                lib.operands_iter_next,
+                lib.dispose_operands_iter,
+                self.module,
+            )
+        };
+        Operands { raw }
+    }
+}
+
+impl<'m> GlobalVariable<'m> {
+    /// Returns if the global is only declared, having no initializer in this module.
+    pub fn is_declaration(&self) -> bool {
+        unsafe { (self.module.ctx.lib.is_declaration)(self.inner) != 0 }
+    }
+
+    /// Returns the attributes of the global.
+    pub fn attributes(&self) -> AttributeSet {
+        let lib = &self.module.ctx.lib;
+        unsafe {
+            AttributeSet::collect(
+                lib,
+                (lib.global_attributes_iter)(self.inner),
+                lib.attribute_set_iter_next,
+                lib.dispose_attribute_set_iter,
+            )
+        }
+    }
+}
+
+impl<'m> Argument<'m> {
+    /// Returns the attributes of the parameter, such as `noundef` or `align 8`.
+    pub fn attributes(&self) -> AttributeSet {
+        let lib = &self.module.ctx.lib;
+        unsafe {
+            AttributeSet::collect(
+                lib,
+                (lib.argument_attributes_iter)(self.inner),
+                lib.attribute_set_iter_next,
+                lib.dispose_attribute_set_iter,
+            )
+        }
+    }
+}
+
+handle_iter! {
+    /// An iterator over the functions in a module, see [Module::functions].
+    Functions, LLVMFunctionsIteratorRef, LLVMValue => Function
+}
+
+handle_iter! {
+    /// An iterator over the global variables in a module, see [Module::globals].
+    Globals, LLVMGlobalsIteratorRef, LLVMValue => GlobalVariable
+}
+
+handle_iter! {
+    /// An iterator over the basic blocks of a function, see [Function::blocks].
+    Blocks, LLVMBlocksIteratorRef, LLVMValue => BasicBlock
+}
+
+handle_iter! {
+    /// An iterator over the parameters of a function, see [Function::arguments].
+    Arguments, LLVMArgumentsIteratorRef, LLVMValue => Argument
+}
+
+handle_iter! {
+    /// An iterator over the instructions of a block, see [BasicBlock::instructions].
+    Instructions, LLVMInstructionsIteratorRef, LLVMValue => Instruction
+}
+
+handle_iter! {
+    /// An iterator over the operands of an instruction, see [Instruction::operands].
+    Operands, LLVMOperandsIteratorRef, LLVMValue => Value
+}
@@ -0,0 +1,179 @@
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+
+/// A single problem reported by the LLVM verifier.
+pub struct VerifyEntry {
+    /// The function the problem was found in, if it could be determined.
+    pub function: Option<String>,
+    /// The first instruction LLVM printed alongside the message, if any.
+    pub instruction: Option<String>,
+    /// The message reported by LLVM.
+    pub message: String,
+}
+
+impl Display for VerifyEntry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "VerifyEntry(function={:?}, instruction={:?}, message={:?})",
+            self.function, self.instruction, self.message,
+        )
+    }
+}
+
+impl Debug for VerifyEntry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self}")
+    }
+}
+
+/// An error produced when a module fails verification.
+pub struct VerifyError {
+    /// Every problem found, in the order LLVM reported them.
+    pub entries: Vec<VerifyEntry>,
+    /// The unparsed output of the verifier.
+    pub raw: String,
+}
+
+impl VerifyError {
+    /// Splits the verifier output into its entries.
+    ///
+    /// The verifier writes each message on its own line, followed by the values
+    /// involved. Instructions are printed indented, other values are printed as
+    /// operands such as `ptr @f`. LLVM does not name the function a message
+    /// belongs to, so it is found by looking up the printed instructions in
+    /// `ir`, the printed form of the module.
+    pub(crate) fn parse(raw: String, ir: &str) -> Self {
+        let functions = functions(ir);
+        let mut entries = Vec::new();
+        let mut lines = raw.lines().filter(|line| !line.trim().is_empty()).peekable();
+
+        while let Some(message) = lines.next() {
+            let mut values = Vec::new();
+            while let Some(line) = lines.next_if(|line| is_value(line)) {
+                values.push(line);
+            }
+
+            let instructions: Vec<&str> = values
+                .iter()
+                .filter(|value| value.starts_with("  "))
+                .map(|value| value.trim())
+                .collect();
+
+            entries.push(VerifyEntry {
+                function: find_function(&functions, &instructions, &values),
+                instruction: instructions.first().map(|i| i.to_string()),
+                message: message.trim().to_string(),
+            });
+        }
+
+        Self { entries, raw }
+    }
+}
+
+impl Display for VerifyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "VerifyError(entries={:?})", self.entries)
+    }
+}
+
+impl Debug for VerifyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self}")
+    }
+}
+
+impl Error for VerifyError {}
+
+/// A function found in the printed module and the trimmed lines of its body.
+struct FunctionBody<'a> {
+    name: &'a str,
+    lines: Vec<&'a str>,
+}
+
+/// Returns if a line of verifier output is a value rather than a new message.
+fn is_value(line: &str) -> bool {
+    // Instructions are indented and types are printed with a leading space.
+    if line.starts_with(' ') {
+        return true;
+    }
+
+    let first = line.split_whitespace().next().unwrap_or_default();
+    if first.starts_with(['%', '@', '!', '{', '[', '<']) {
+        return true;
+    }
+
+    let is_int = first
+        .strip_prefix('i')
+        .is_some_and(|bits| !bits.is_empty() && bits.bytes().all(|b| b.is_ascii_digit()));
+    is_int
+        || matches!(
+            first,
+            "ptr" | "label" | "void" | "metadata" | "token" | "half" | "float" | "double"
+        )
+}
+
+/// Collects every function defined or declared in the printed module.
+fn functions(ir: &str) -> Vec<FunctionBody<'_>> {
+    let mut functions = Vec::new();
+    let mut lines = ir.lines();
+
+    while let Some(line) = lines.next() {
+        let is_define = line.starts_with("define ");
+        if !is_define && !line.starts_with("declare ") {
+            continue;
+        }
+        let Some(name) = function_name(line) else {
+            continue;
+        };
+
+        let mut body = Vec::new();
+        if is_define {
+            for line in lines.by_ref().take_while(|line| *line != "}") {
+                body.push(line.trim());
+            }
+        }
+
+        functions.push(FunctionBody { name, lines: body });
+    }
+
+    functions
+}
+
+/// Extracts the name from a `define` or `declare` line, without the `@`.
+fn function_name(line: &str) -> Option<&str> {
+    let start = line.find('@')? + 1;
+    let rest = &line[start..];
+
+    if let Some(quoted) = rest.strip_prefix('"') {
+        return quoted.split('"').next();
+    }
+    rest.split('(').next()
+}
+
+/// Works out which function an entry belongs to.
+///
+/// The function containing every printed instruction is used if there is exactly
+/// one, otherwise the first printed operand referring to a function is.
+fn find_function(
+    functions: &[FunctionBody],
+    instructions: &[&str],
+    values: &[&str],
+) -> Option<String> {
+    if !instructions.is_empty() {
+        let mut matches = functions.iter().filter(|function| {
+            instructions.iter().all(|instruction| function.lines.contains(instruction))
+        });
+
+        if let (Some(function), None) = (matches.next(), matches.next()) {
+            return Some(function.name.to_string());
+        }
+    }
+
+    values
+        .iter()
+        .filter_map(|value| value.split_once('@'))
+        .map(|(_, name)| name.trim_matches('"'))
+        .find(|name| functions.iter().any(|function| function.name == *name))
+        .map(|name| name.to_string())
+}
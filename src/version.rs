@@ -0,0 +1,39 @@
+use std::fmt::{Display, Formatter};
+
+/// The oldest LLVM major version this crate supports.
+pub const MIN_LLVM_MAJOR: u32 = 14;
+/// The newest LLVM major version this crate supports.
+pub const MAX_LLVM_MAJOR: u32 = 15;
+
+/// The version of LLVM a llvmlite library was built against.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LlvmVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl LlvmVersion {
+    /// Decodes the version packed by `LLVMPY_GetVersionInfo`.
+    ///
+    /// The major, minor and patch components are packed into the
+    /// low three bytes, i.e. `major << 16 | minor << 8 | patch`.
+    pub(crate) fn from_packed(packed: u32) -> Self {
+        Self {
+            major: (packed >> 16) & 0xFF,
+            minor: (packed >> 8) & 0xFF,
+            patch: packed & 0xFF,
+        }
+    }
+
+    /// Returns if this crate supports libraries built against this version.
+    pub fn is_supported(&self) -> bool {
+        (MIN_LLVM_MAJOR..=MAX_LLVM_MAJOR).contains(&self.major)
+    }
+}
+
+impl Display for LlvmVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
@@ -1,24 +1,72 @@
-use std::ffi::{c_char, CStr};
+use std::error::Error;
+use std::ffi::{c_char, CStr, CString, NulError};
 use std::fmt::{Debug, Display, Formatter};
+use std::io;
 use std::ops::Deref;
 use std::ptr;
+use std::slice;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
 
 use llvmlite_types::LLVMContextRef;
 
-use crate::binding::LLVMMethods;
+use crate::binding::{LLVMMethods, GLOBAL_CONTEXT_TAKEN};
+use crate::module::{IrParseError, Module, ParseError};
+
+/// An error produced when a string cannot be passed to or read back from LLVM.
+pub enum StringError {
+    /// The string contains a NUL byte, which LLVM would treat as its end.
+    InteriorNul {
+        /// The position of the first NUL byte.
+        position: usize,
+    },
+    /// LLVM produced a string which is not valid UTF-8.
+    InvalidUtf8,
+}
+
+impl Display for StringError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StringError::InteriorNul { position } => {
+                write!(f, "StringError(interior_nul, position={position})")
+            },
+            StringError::InvalidUtf8 => write!(f, "StringError(invalid_utf8)"),
+        }
+    }
+}
+
+impl Debug for StringError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self}")
+    }
+}
+
+impl Error for StringError {}
+
+impl From<NulError> for StringError {
+    fn from(e: NulError) -> Self {
+        StringError::InteriorNul {
+            position: e.nul_position(),
+        }
+    }
+}
 
 /// A UTF-8 string allocated by LLVM.
 ///
-/// The string lives for as long as the DLL is loaded or until
-/// the value is dropped.
-pub struct Utf8String<'lib> {
+/// The string keeps the DLL loaded and lives until the value is dropped.
+pub struct Utf8String {
     pub(crate) inner: &'static str,
     pub(crate) ptr: *const c_char,
-    pub(crate) lib: LLVMMethods<'lib>,
+    pub(crate) lib: Arc<LLVMMethods>,
 }
 
-impl<'lib> Utf8String<'lib> {
-    pub(crate) fn empty(lib: LLVMMethods<'lib>) -> Self {
+// SAFETY: The string is an immutable allocation owned by this value and
+// `LLVMPY_DisposeString` can be called from any thread.
+unsafe impl Send for Utf8String {}
+unsafe impl Sync for Utf8String {}
+
+impl Utf8String {
+    pub(crate) fn empty(lib: Arc<LLVMMethods>) -> Self {
         Self {
             inner: "",
             ptr: ptr::null(),
@@ -27,25 +75,25 @@ impl<'lib> Utf8String<'lib> {
     }
 }
 
-impl<'lib> Display for Utf8String<'lib> {
+impl Display for Utf8String {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.as_ref())
     }
 }
 
-impl<'lib> Debug for Utf8String<'lib> {
+impl Debug for Utf8String {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{:?}", self.as_ref())
     }
 }
 
-impl<'lib> AsRef<str> for Utf8String<'lib> {
+impl AsRef<str> for Utf8String {
     fn as_ref(&self) -> &str {
         self.inner
     }
 }
 
-impl<'lib> Deref for Utf8String<'lib> {
+impl Deref for Utf8String {
     type Target = str;
 
     fn deref(&self) -> &Self::Target {
@@ -53,7 +101,7 @@ impl<'lib> Deref for Utf8String<'lib> {
     }
 }
 
-impl<'lib> Drop for Utf8String<'lib> {
+impl Drop for Utf8String {
     fn drop(&mut self) {
         if !self.ptr.is_null() {
             unsafe { (self.lib.dispose_string)(self.ptr) };
@@ -66,31 +114,34 @@ impl<'lib> Drop for Utf8String<'lib> {
 
 /// A non-utf-8 byte string allocated by LLVM.
 ///
-/// The string lives for as long as the DLL is loaded or until
-/// the value is dropped.
-pub struct ByteString<'lib> {
+/// The string keeps the DLL loaded and lives until the value is dropped.
+pub struct ByteString {
     pub(crate) inner: Option<&'static CStr>,
     pub(crate) ptr: *const c_char,
-    pub(crate) lib: LLVMMethods<'lib>,
+    pub(crate) lib: Arc<LLVMMethods>,
 }
 
-impl<'lib> ByteString<'lib> {
-    pub(crate) fn empty(lib: LLVMMethods<'lib>) -> Self {
+// SAFETY: The same reasoning as `Utf8String` applies.
+unsafe impl Send for ByteString {}
+unsafe impl Sync for ByteString {}
+
+impl ByteString {
+    pub(crate) fn empty(lib: Arc<LLVMMethods>) -> Self {
         Self {
             inner: None,
             ptr: ptr::null(),
-            lib: lib.clone(),
+            lib,
         }
     }
 }
 
-impl<'lib> Debug for ByteString<'lib> {
+impl Debug for ByteString {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{:?}", self.as_ref())
     }
 }
 
-impl<'lib> AsRef<CStr> for ByteString<'lib> {
+impl AsRef<CStr> for ByteString {
     fn as_ref(&self) -> &CStr {
         self.inner
             .as_ref()
@@ -98,7 +149,7 @@ impl<'lib> AsRef<CStr> for ByteString<'lib> {
     }
 }
 
-impl<'lib> Deref for ByteString<'lib> {
+impl Deref for ByteString {
     type Target = CStr;
 
     fn deref(&self) -> &Self::Target {
@@ -108,7 +159,7 @@ impl<'lib> Deref for ByteString<'lib> {
     }
 }
 
-impl<'lib> Drop for ByteString<'lib> {
+impl Drop for ByteString {
     fn drop(&mut self) {
         if !self.ptr.is_null() {
             unsafe { (self.lib.dispose_string)(self.ptr) };
@@ -119,20 +170,181 @@ impl<'lib> Drop for ByteString<'lib> {
 }
 
 
+/// A length aware byte buffer allocated by LLVM, such as bitcode.
+///
+/// Unlike [ByteString] the contents may contain NUL bytes. The buffer keeps
+/// the DLL loaded and lives until the value is dropped.
+pub struct LlvmBytes {
+    pub(crate) ptr: *const c_char,
+    pub(crate) len: usize,
+    pub(crate) lib: Arc<LLVMMethods>,
+}
+
+// SAFETY: The same reasoning as `Utf8String` applies.
+unsafe impl Send for LlvmBytes {}
+unsafe impl Sync for LlvmBytes {}
+
+impl LlvmBytes {
+    /// Takes ownership of a buffer LLVM allocated with `len` bytes, which is
+    /// freed with `LLVMPY_DisposeString` when dropped.
+    pub(crate) unsafe fn from_raw(
+        lib: Arc<LLVMMethods>,
+        ptr: *const c_char,
+        len: usize,
+    ) -> Self {
+        Self { ptr, len, lib }
+    }
+}
+
+impl Debug for LlvmBytes {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.as_ref())
+    }
+}
+
+impl AsRef<[u8]> for LlvmBytes {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+impl Deref for LlvmBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        if self.ptr.is_null() {
+            return &[];
+        }
+
+        unsafe { slice::from_raw_parts(self.ptr as *const u8, self.len) }
+    }
+}
+
+impl Drop for LlvmBytes {
+    fn drop(&mut self) {
+        if !self.ptr.is_null() {
+            unsafe { (self.lib.dispose_string)(self.ptr) };
+            self.ptr = ptr::null();
+            self.len = 0;
+        }
+    }
+}
+
+
 /// A safe wrapper around a LLVM context.
-pub struct Context<'lib> {
+///
+/// # Thread safety
+/// A context can be moved to another thread but not shared between threads,
+/// and any IR created from it stays on the thread that owns the context.
+///
+/// ```
+/// # fn check(lib: &llvmlite::LLVMLite) {
+/// let ctx = lib.create_context();
+/// std::thread::spawn(move || {
+///     let _module = ctx.parse_assembly("").unwrap();
+/// });
+/// # }
+/// ```
+///
+/// A context cannot be used from two threads at once:
+///
+/// ```compile_fail,E0277
+/// # fn check(lib: &llvmlite::LLVMLite) {
+/// let ctx = lib.create_context();
+/// std::thread::scope(|s| {
+///     s.spawn(|| drop(ctx.parse_assembly("")));
+///     s.spawn(|| drop(ctx.parse_assembly("")));
+/// });
+/// # }
+/// ```
+///
+/// And a module cannot be moved to another thread without its context:
+///
+/// ```compile_fail,E0277
+/// # fn check(lib: &llvmlite::LLVMLite) {
+/// let ctx = lib.create_context();
+/// let module = ctx.parse_assembly("").unwrap();
+/// std::thread::scope(|s| {
+///     s.spawn(move || drop(module));
+/// });
+/// # }
+/// ```
+pub struct Context {
     pub(crate) is_global: bool,
     pub(crate) inner: LLVMContextRef,
-    pub(crate) lib: LLVMMethods<'lib>,
+    pub(crate) lib: Arc<LLVMMethods>,
 }
 
-impl<'lib> Context<'lib> {
+// SAFETY: LLVM contexts may be used from any thread as long as they are never
+// used from two threads at the same time. `Context` stays `!Sync` through its raw
+// pointer, and everything that borrows it is therefore `!Send`, so a context and
+// all IR created in it can only ever change threads together.
+unsafe impl Send for Context {}
+
+impl Context {
+    /// Parses a module from its textual LLVM IR representation.
+    ///
+    /// Errors are located in `ir`, see [IrParseError].
+    pub fn parse_assembly(&self, ir: &str) -> Result<Module<'_>, IrParseError> {
+        let c_ir = CString::new(ir).map_err(|e| {
+            let message = "assembly contains a null byte".to_string();
+            IrParseError::at_offset(ir, e.nul_position(), message)
+        })?;
 
+        let mut err: *const c_char = ptr::null();
+        let module = unsafe { (self.lib.parse_assembly)(self.inner, c_ir.as_ptr(), &mut err) };
+
+        if module.is_null() {
+            let diagnostic = unsafe { ParseError::from_llvm(&self.lib, err) };
+            return Err(IrParseError::from_diagnostic(&diagnostic.message, ir));
+        }
+
+        Ok(Module {
+            inner: module,
+            ctx: self,
+        })
+    }
+
+    /// Parses a module from a LLVM bitcode buffer.
+    pub fn parse_bitcode(&self, bitcode: &[u8]) -> Result<Module<'_>, ParseError> {
+        let mut err: *const c_char = ptr::null();
+        let module = unsafe {
+            (self.lib.parse_bitcode)(
+                self.inner,
+                bitcode.as_ptr() as *const c_char,
+                bitcode.len(),
+                &mut err,
+            )
+        };
+
+        if module.is_null() {
+            return Err(unsafe { ParseError::from_llvm(&self.lib, err) });
+        }
+
+        Ok(Module {
+            inner: module,
+            ctx: self,
+        })
+    }
+
+    /// Reads all of `reader` and parses it as a LLVM bitcode buffer.
+    ///
+    /// If the bitcode is invalid an error of kind [io::ErrorKind::InvalidData]
+    /// wrapping the [ParseError] is returned.
+    pub fn read_bitcode(&self, mut reader: impl io::Read) -> io::Result<Module<'_>> {
+        let mut bitcode = Vec::new();
+        reader.read_to_end(&mut bitcode)?;
+
+        self.parse_bitcode(&bitcode)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
 }
 
-impl<'lib> Drop for Context<'lib> {
+impl Drop for Context {
     fn drop(&mut self) {
-        if !self.inner.is_null() && !self.is_global {
+        if self.is_global {
+            GLOBAL_CONTEXT_TAKEN.store(false, Ordering::Release);
+        } else if !self.inner.is_null() {
             unsafe { (self.lib.context_dispose)(self.inner) }
         }
     }
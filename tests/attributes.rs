@@ -0,0 +1,201 @@
+mod common;
+
+use llvmlite::{Attribute, AttributeError, AttributeSet};
+
+const IR: &str = "\
+@counter = global i32 0 #2
+
+declare i32 @puts(ptr noundef nonnull dereferenceable(1)) nounwind
+
+define noundef i32 @bump(i32 noundef %by, ptr noalias align 8 %msg) #0 {
+entry:
+  %printed = call noundef i32 @puts(ptr noundef nonnull %msg) #1
+  ret i32 %printed
+}
+
+attributes #0 = { noinline nounwind optnone \"frame-pointer\"=\"all\" }
+attributes #1 = { cold }
+attributes #2 = { \"bss-section\"=\".counters\" }
+";
+
+#[test]
+fn test_function_attributes() {
+    let lib = common::load();
+    let ctx = lib.create_context();
+    let module = ctx.parse_assembly(IR).expect("parse IR");
+
+    let bump = module.get_function("bump").unwrap().unwrap();
+    let attributes = bump.attributes();
+    assert!(attributes.contains(Attribute::NoUnwind));
+    assert!(attributes.contains(&Attribute::NoInline));
+    assert!(attributes.contains(Attribute::OptimizeNone));
+    assert!(!attributes.contains(Attribute::AlwaysInline));
+    assert_eq!(attributes.string_value("frame-pointer"), Some("all"));
+    assert_eq!(attributes.string_value("target-cpu"), None);
+
+    // Return value and parameter attributes are kept apart.
+    assert!(!attributes.contains(Attribute::NoUndef));
+    assert!(!attributes.contains(Attribute::NoAlias));
+    assert!(!attributes.contains(Attribute::Align(8)));
+    let returned = bump.return_attributes();
+    assert_eq!(returned.iter().collect::<Vec<_>>(), [&Attribute::NoUndef]);
+    assert_eq!(common::live_strings(), 0, "attribute strings should be disposed");
+    assert_eq!(common::live_iterators(), 0);
+
+    let puts = module.get_function("puts").unwrap().unwrap();
+    assert!(puts.attributes().contains(Attribute::NoUnwind));
+    assert!(puts.return_attributes().is_empty());
+}
+
+#[test]
+fn test_argument_attributes() {
+    let lib = common::load();
+    let ctx = lib.create_context();
+    let module = ctx.parse_assembly(IR).expect("parse IR");
+
+    let bump = module.get_function("bump").unwrap().unwrap();
+    let sets: Vec<_> = bump.arguments().map(|a| a.attributes()).collect();
+    assert_eq!(sets[0].iter().collect::<Vec<_>>(), [&Attribute::NoUndef]);
+    assert_eq!(
+        sets[1].iter().collect::<Vec<_>>(),
+        [&Attribute::NoAlias, &Attribute::Align(8)]
+    );
+
+    let puts = module.get_function("puts").unwrap().unwrap();
+    let set = puts.arguments().next().unwrap().attributes();
+    let expected = [Attribute::NoUndef, Attribute::NonNull, Attribute::Dereferenceable(1)];
+    assert_eq!(set.into_iter().collect::<Vec<_>>(), expected);
+    assert_eq!(common::live_strings(), 0);
+    assert_eq!(common::live_iterators(), 0);
+}
+
+#[test]
+fn test_call_site_and_global_attributes() {
+    let lib = common::load();
+    let ctx = lib.create_context();
+    let module = ctx.parse_assembly(IR).expect("parse IR");
+
+    let bump = module.get_function("bump").unwrap().unwrap();
+    let entry = bump.blocks().next().unwrap();
+    let sets: Vec<_> = entry.instructions().map(|i| i.attributes()).collect();
+    let call = sets[0].as_ref().expect("calls have attributes");
+    assert!(call.contains(Attribute::Cold));
+    assert_eq!(call.len(), 1, "argument attributes are kept apart");
+    assert!(!call.contains(Attribute::NoUndef));
+    assert!(sets[1].is_none(), "ret is not a call site");
+
+    let returned: Vec<_> = entry.instructions().map(|i| i.return_attributes()).collect();
+    let call = returned[0].as_ref().expect("calls have return attributes");
+    assert_eq!(call.iter().collect::<Vec<_>>(), [&Attribute::NoUndef]);
+    assert!(returned[1].is_none());
+
+    let counter = module.get_global("counter").unwrap().unwrap();
+    assert_eq!(counter.attributes().string_value("bss-section"), Some(".counters"));
+    assert_eq!(common::live_iterators(), 0);
+}
+
+#[test]
+fn test_parse_attributes() {
+    let set: AttributeSet = "nounwind align 16 alignstack(8) dereferenceable_or_null(4) \
+        byval({ i32, i32 }) sret(%\"a b\") \"no-trapping-math\" \"key\"=\"a\\22b\\5Cc\" \
+        uwtable(sync) nounwind"
+        .parse()
+        .unwrap();
+
+    let expected = [
+        Attribute::NoUnwind,
+        Attribute::Align(16),
+        Attribute::AlignStack(8),
+        Attribute::DereferenceableOrNull(4),
+        Attribute::ByVal("{ i32, i32 }".to_string()),
+        Attribute::StructRet("%\"a b\"".to_string()),
+        Attribute::String { key: "no-trapping-math".to_string(), value: String::new() },
+        Attribute::String { key: "key".to_string(), value: "a\"b\\c".to_string() },
+        Attribute::Other("uwtable(sync)".to_string()),
+    ];
+    assert_eq!(set.iter().cloned().collect::<Vec<_>>(), expected, "duplicates are dropped");
+
+    // Display writes attributes back the way LLVM prints them.
+    let printed: Vec<_> = expected.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        printed,
+        [
+            "nounwind",
+            "align 16",
+            "alignstack(8)",
+            "dereferenceable_or_null(4)",
+            "byval({ i32, i32 })",
+            "sret(%\"a b\")",
+            "\"no-trapping-math\"",
+            "\"key\"=\"a\\22b\\5Cc\"",
+            "uwtable(sync)",
+        ]
+    );
+    for attribute in expected {
+        assert_eq!(attribute.to_string().parse::<Attribute>().unwrap(), attribute);
+    }
+
+    assert_eq!(Attribute::NoUnwind.enum_name(), Some("nounwind"));
+    assert_eq!(Attribute::Align(4).enum_name(), None);
+}
+
+#[test]
+fn test_add_attribute() {
+    let lib = common::load();
+    let ctx = lib.create_context();
+    let module = ctx.parse_assembly(IR).expect("parse IR");
+
+    let bump = module.get_function("bump").unwrap().unwrap();
+    assert!(!bump.attributes().contains(Attribute::AlwaysInline));
+
+    bump.add_attribute("alwaysinline").expect("add by name");
+    bump.add_attribute(Attribute::Cold).expect("add typed attribute");
+
+    let attributes = bump.attributes();
+    assert!(attributes.contains(Attribute::AlwaysInline));
+    assert!(attributes.contains(Attribute::Cold));
+    assert!(attributes.contains(Attribute::NoUnwind), "existing attributes are kept");
+    assert_eq!(common::calls("LLVMPY_AddFunctionAttr"), 2);
+
+    // Attributes can be added while walking the module.
+    let name = bump.name();
+    for function in module.functions() {
+        function.add_attribute(Attribute::NoRecurse).expect("add while iterating");
+    }
+    let puts = module.get_function("puts").unwrap().unwrap();
+    assert!(puts.attributes().contains(Attribute::NoRecurse));
+    assert_eq!(name, "bump", "borrowed names stay valid");
+    assert_eq!(common::live_iterators(), 0);
+}
+
+#[test]
+fn test_add_unknown_attribute() {
+    let lib = common::load();
+    let ctx = lib.create_context();
+    let module = ctx.parse_assembly(IR).expect("parse IR");
+    let bump = module.get_function("bump").unwrap().unwrap();
+
+    let err = bump.add_attribute("alwaysinlined").expect_err("unknown name");
+    assert!(matches!(&err, AttributeError::UnknownAttribute { name } if name == "alwaysinlined"));
+    assert_eq!(err.to_string(), "AttributeError(unknown_attribute=\"alwaysinlined\")");
+
+    // Attributes without a kind are rejected before asking LLVM for one.
+    let err = bump.add_attribute(Attribute::Align(8)).expect_err("align needs a value");
+    assert!(matches!(err, AttributeError::RequiresValue { attribute: Attribute::Align(8) }));
+    assert!(bump.add_attribute("align").is_err());
+
+    // `uwtable` is an int attribute from LLVM 15, so it is not added by kind.
+    let err = bump.add_attribute(Attribute::UWTable).expect_err("uwtable carries a value");
+    assert!(matches!(err, AttributeError::RequiresValue { attribute: Attribute::UWTable }));
+    assert!(bump.add_attribute("uwtable").is_err());
+
+    let err = bump
+        .add_attribute("\"frame-pointer\"=\"none\"")
+        .expect_err("string attributes have no kind");
+    let expected = "frame-pointer";
+    assert!(matches!(&err, AttributeError::StringAttribute { key } if key == expected));
+    assert_eq!(err.to_string(), "AttributeError(string_attribute=\"frame-pointer\")");
+
+    assert_eq!(common::calls("LLVMPY_GetEnumAttributeKindForName"), 1);
+    assert_eq!(common::calls("LLVMPY_AddFunctionAttr"), 0);
+}
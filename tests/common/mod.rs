@@ -0,0 +1,109 @@
+//! Helpers for driving the `llvmlite-stub` library from tests.
+#![allow(dead_code)]
+
+use std::env;
+use std::ffi::{c_char, CString};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::OnceLock;
+
+use libloading::Library;
+use llvmlite::LLVMLite;
+
+/// Returns the path of the stub library, building it the first time.
+///
+/// The stub is built into its own target directory so the nested cargo
+/// invocation does not wait on the lock held by the outer one.
+pub fn path() -> &'static Path {
+    static PATH: OnceLock<PathBuf> = OnceLock::new();
+
+    PATH.get_or_init(|| {
+        let exe = env::current_exe().expect("test executable path");
+        let target_dir = exe
+            .ancestors()
+            .nth(3)
+            .expect("test executable should live in <target>/<profile>/deps")
+            .join("llvmlite-stub");
+
+        let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+        let status = Command::new(cargo)
+            .current_dir(env!("CARGO_MANIFEST_DIR"))
+            .args(["build", "--quiet", "--package", "llvmlite-stub", "--target-dir"])
+            .arg(&target_dir)
+            .status()
+            .expect("failed to run cargo");
+        assert!(status.success(), "failed to build llvmlite-stub");
+
+        target_dir
+            .join("debug")
+            .join(libloading::library_filename("llvmlite_stub"))
+    })
+}
+
+/// Loads the stub through the binding after resetting the current thread's state.
+pub fn load() -> LLVMLite {
+    reset();
+    unsafe { LLVMLite::from_path(path()).expect("stub should load") }
+}
+
+fn control() -> &'static Library {
+    static LIBRARY: OnceLock<Library> = OnceLock::new();
+    LIBRARY.get_or_init(|| unsafe { Library::new(path()).expect("stub should load") })
+}
+
+unsafe fn symbol<T: Copy>(name: &str) -> T {
+    *control()
+        .get::<T>(name.as_bytes())
+        .unwrap_or_else(|e| panic!("stub is missing {name}: {e}"))
+}
+
+pub fn reset() {
+    unsafe { symbol::<unsafe extern "C" fn()>("llvmlite_stub_reset")() }
+}
+
+/// Returns how many times the current thread called the given `LLVMPY_*` symbol.
+pub fn calls(name: &str) -> usize {
+    let name = CString::new(name).unwrap();
+    unsafe {
+        symbol::<unsafe extern "C" fn(*const c_char) -> usize>("llvmlite_stub_calls")(
+            name.as_ptr(),
+        )
+    }
+}
+
+/// Makes the next call to the given `LLVMPY_*` symbol fail with `message`.
+pub fn fail_next(name: &str, message: &str) {
+    let name = CString::new(name).unwrap();
+    let message = CString::new(message).unwrap();
+    unsafe {
+        symbol::<unsafe extern "C" fn(*const c_char, *const c_char)>(
+            "llvmlite_stub_fail_next",
+        )(name.as_ptr(), message.as_ptr())
+    }
+}
+
+/// Sets the LLVM version the stub reports, for the current thread.
+pub fn set_version(major: u32, minor: u32, patch: u32) {
+    let packed = (major << 16) | (minor << 8) | patch;
+    unsafe { symbol::<unsafe extern "C" fn(u32)>("llvmlite_stub_set_version")(packed) }
+}
+
+pub fn live_strings() -> usize {
+    unsafe { symbol::<unsafe extern "C" fn() -> usize>("llvmlite_stub_live_strings")() }
+}
+
+pub fn live_contexts() -> usize {
+    unsafe { symbol::<unsafe extern "C" fn() -> usize>("llvmlite_stub_live_contexts")() }
+}
+
+pub fn live_modules() -> usize {
+    unsafe { symbol::<unsafe extern "C" fn() -> usize>("llvmlite_stub_live_modules")() }
+}
+
+pub fn live_iterators() -> usize {
+    unsafe { symbol::<unsafe extern "C" fn() -> usize>("llvmlite_stub_live_iterators")() }
+}
+
+pub fn invalid_frees() -> usize {
+    unsafe { symbol::<unsafe extern "C" fn() -> usize>("llvmlite_stub_invalid_frees")() }
+}
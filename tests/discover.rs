@@ -0,0 +1,27 @@
+mod common;
+
+use std::env;
+
+use llvmlite::{LLVMLite, LoadError, LIBRARY_ENV_VAR};
+
+// Kept as a single test as it modifies the process environment.
+#[test]
+fn test_discover_env_var() {
+    env::set_var(LIBRARY_ENV_VAR, common::path());
+    common::reset();
+    unsafe { LLVMLite::discover() }.expect("library should be found via the env var");
+
+    let missing = env::temp_dir().join("llvmlite-rs-missing").join("libllvmlite.so");
+    env::set_var(LIBRARY_ENV_VAR, &missing);
+    match unsafe { LLVMLite::discover() } {
+        Err(LoadError::NotFound { tried }) => {
+            assert_eq!(tried.first(), Some(&missing));
+            assert!(tried.len() > 1, "the system paths should also be tried");
+        },
+        // The host may have a real llvmlite installed.
+        Ok(_) => {},
+        Err(other) => panic!("unexpected error: {other}"),
+    }
+
+    env::remove_var(LIBRARY_ENV_VAR);
+}
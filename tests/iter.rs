@@ -0,0 +1,116 @@
+mod common;
+
+use llvmlite::StringError;
+
+const IR: &str = "\
+%Point = type { i32, i32 }
+%\"Named Pair\" = type { i8, i8 }
+
+@counter = global i32 0
+@message = constant [6 x i8] c\"hello\\00\"
+
+declare i32 @puts(ptr)
+
+define i32 @main() {
+entry:
+  ret i32 0
+}
+";
+
+#[test]
+fn test_functions() {
+    let lib = common::load();
+    let ctx = lib.create_context();
+    let module = ctx.parse_assembly(IR).expect("parse IR");
+
+    let names: Vec<_> = module.functions().map(|f| f.name().into_owned()).collect();
+    assert_eq!(names, ["puts", "main"]);
+    assert_eq!(common::live_iterators(), 0);
+    assert_eq!(common::calls("LLVMPY_DisposeFunctionsIter"), 1);
+}
+
+#[test]
+fn test_globals() {
+    let lib = common::load();
+    let ctx = lib.create_context();
+    let module = ctx.parse_assembly(IR).expect("parse IR");
+
+    let names: Vec<_> = module.globals().map(|g| g.name().into_owned()).collect();
+    assert_eq!(names, ["counter", "message"]);
+    assert_eq!(common::live_iterators(), 0);
+}
+
+#[test]
+fn test_struct_types() {
+    let lib = common::load();
+    let ctx = lib.create_context();
+    let module = ctx.parse_assembly(IR).expect("parse IR");
+
+    let names: Vec<_> = module.struct_types().map(|t| t.name()).collect();
+    assert_eq!(names, ["Point", "Named Pair"]);
+    assert_eq!(common::live_iterators(), 0);
+    assert_eq!(common::live_strings(), 0, "type names should be disposed");
+}
+
+#[test]
+fn test_iterator_disposed_on_break() {
+    let lib = common::load();
+    let ctx = lib.create_context();
+    let module = ctx.parse_assembly(IR).expect("parse IR");
+
+    let mut functions = module.functions();
+    assert_eq!(common::live_iterators(), 1);
+    for function in functions.by_ref() {
+        if function.name() == "puts" {
+            break;
+        }
+    }
+    assert_eq!(common::live_iterators(), 1);
+    drop(functions);
+
+    assert_eq!(common::live_iterators(), 0);
+    assert_eq!(common::invalid_frees(), 0);
+}
+
+#[test]
+fn test_handles_outlive_iterator() {
+    let lib = common::load();
+    let ctx = lib.create_context();
+    let module = ctx.parse_assembly(IR).expect("parse IR");
+
+    let main = module.functions().last().expect("module has functions");
+    assert_eq!(common::live_iterators(), 0);
+    assert_eq!(main.name(), "main");
+    assert!(std::ptr::eq(main.module(), &module));
+}
+
+#[test]
+fn test_named_lookup() {
+    let lib = common::load();
+    let ctx = lib.create_context();
+    let module = ctx.parse_assembly(IR).expect("parse IR");
+
+    let main = module.get_function("main").unwrap().expect("main is defined");
+    assert_eq!(main.name(), "main");
+    let counter = module.get_global("counter").unwrap().expect("counter is defined");
+    assert_eq!(counter.name(), "counter");
+    let pair = module.get_struct_type("Named Pair").unwrap().expect("type is defined");
+    assert_eq!(pair.name(), "Named Pair");
+
+    assert!(module.get_function("missing").unwrap().is_none());
+    assert!(module.get_global("main").unwrap().is_none());
+    assert!(module.get_struct_type("Missing").unwrap().is_none());
+}
+
+#[test]
+fn test_named_lookup_interior_nul() {
+    let lib = common::load();
+    let ctx = lib.create_context();
+    let module = ctx.parse_assembly(IR).expect("parse IR");
+
+    let err = module.get_function("ma\0in").unwrap_err();
+    assert!(matches!(err, StringError::InteriorNul { position: 2 }), "{err}");
+    assert!(module.get_global("\0").is_err());
+    assert!(module.get_struct_type("Point\0").is_err());
+    assert_eq!(common::calls("LLVMPY_GetNamedFunction"), 0);
+}
@@ -0,0 +1,151 @@
+mod common;
+
+use libloading::Library;
+use llvmlite::{Capability, LLVMLite, LlvmVersion, LoadError, SymbolStatus};
+
+#[test]
+fn test_load_reports_version() {
+    let lib = common::load();
+    assert_eq!(
+        lib.llvm_version(),
+        LlvmVersion {
+            major: 14,
+            minor: 0,
+            patch: 6
+        },
+    );
+}
+
+#[test]
+fn test_load_rejects_unsupported_version() {
+    common::reset();
+    common::set_version(9, 0, 1);
+
+    let err = unsafe { LLVMLite::from_path(common::path()) }
+        .err()
+        .expect("LLVM 9 should be rejected");
+    match err {
+        LoadError::IncompatibleVersion { found } => assert_eq!(found.to_string(), "9.0.1"),
+        other => panic!("unexpected error: {other}"),
+    }
+}
+
+#[test]
+fn test_load_missing_library() {
+    let err = unsafe { LLVMLite::from_path("/definitely/not/libllvmlite.so") }
+        .err()
+        .expect("library should not exist");
+    assert!(matches!(err, LoadError::LoadLLVM { .. }), "unexpected error: {err}");
+}
+
+#[test]
+fn test_missing_symbols_are_aggregated() {
+    // The stub is loaded privately by the other tests, so the test binary
+    // itself provides none of the llvmlite symbols.
+    let err = unsafe { LLVMLite::from_current_process() }
+        .err()
+        .expect("the test binary is not llvmlite");
+    let LoadError::MissingSymbols { missing, path, version } = err else {
+        panic!("unexpected error: {err}");
+    };
+
+    let count = |name: &str| missing.iter().filter(|m| **m == name).count();
+    assert_eq!(count("LLVMPY_GetVersionInfo"), 1);
+    assert_eq!(count("LLVMPY_ParseAssembly"), 1);
+    assert_eq!(count("LLVMPY_GetOpcodeName"), 1);
+    for name in &missing {
+        assert_eq!(count(name), 1, "{name} is listed more than once");
+    }
+    assert!(!missing.contains(&"LLVMPY_AddGVNPass"), "optional symbols are not required");
+    assert_eq!(path, None);
+    assert_eq!(version, None);
+}
+
+#[test]
+fn test_probe_reports_symbols() {
+    let library = unsafe { Library::new(common::path()) }.unwrap();
+    let symbols = unsafe { LLVMLite::probe(&library) };
+
+    let status = |name: &str| *symbols.iter().find(|s| s.name == name).unwrap();
+    assert_eq!(
+        status("LLVMPY_ParseAssembly"),
+        SymbolStatus {
+            name: "LLVMPY_ParseAssembly",
+            capability: None,
+            provided: true,
+        },
+    );
+    assert_eq!(
+        status("LLVMPY_AddGVNPass"),
+        SymbolStatus {
+            name: "LLVMPY_AddGVNPass",
+            capability: Some(Capability::Passes),
+            provided: false,
+        },
+    );
+
+    for status in &symbols {
+        let count = symbols.iter().filter(|s| s.name == status.name).count();
+        assert_eq!(count, 1, "{} is listed more than once", status.name);
+    }
+
+    let lib = common::load();
+    assert_eq!(lib.symbols(), symbols);
+}
+
+#[test]
+fn test_optional_capabilities() {
+    // The stub only exports the required symbols.
+    let lib = common::load();
+    assert!(!lib.supports(Capability::Passes));
+    assert!(!lib.supports(Capability::Svml));
+    assert!(!lib.supports(Capability::RefPrune));
+    assert!(!lib.supports(Capability::JitEvents));
+    assert!(!lib.supports(Capability::DotPrinters));
+
+    // Calling into a missing capability is an error rather than a crash.
+    let err = lib.has_svml_support().expect_err("the stub has no SVML check");
+    assert_eq!(err.name, "LLVMPY_HasSVMLSupport");
+    assert_eq!(err.capability, Capability::Svml);
+    assert_eq!(
+        err.to_string(),
+        "MissingSymbol(function=\"LLVMPY_HasSVMLSupport\", capability=Svml)"
+    );
+}
+
+#[test]
+fn test_global_context_single_handle() {
+    let lib = common::load();
+
+    let ctx = lib.get_global_context().expect("global context should be free");
+    assert!(lib.get_global_context().is_none());
+    drop(ctx);
+
+    assert!(lib.get_global_context().is_some());
+    assert_eq!(common::calls("LLVMPY_ContextDispose"), 0);
+    assert_eq!(common::invalid_frees(), 0);
+
+    // Loading the library again shares the same LLVM global context, so the
+    // guard has to span both. This is part of the single library test because
+    // the guard is process-wide and tests run in parallel.
+    let other = unsafe { LLVMLite::from_path(common::path()).expect("stub should load") };
+    let ctx = lib.get_global_context().expect("global context should be free");
+    assert!(other.get_global_context().is_none());
+    drop(ctx);
+
+    let ctx = other.get_global_context().expect("global context should be free");
+    assert!(lib.get_global_context().is_none());
+    drop(ctx);
+}
+
+#[test]
+fn test_context_dispose_on_drop() {
+    let lib = common::load();
+
+    let ctx = lib.create_context();
+    assert_eq!(common::live_contexts(), 1);
+    drop(ctx);
+
+    assert_eq!(common::live_contexts(), 0);
+    assert_eq!(common::calls("LLVMPY_ContextDispose"), 1);
+}
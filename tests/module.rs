@@ -0,0 +1,355 @@
+mod common;
+
+use std::io::{self, Cursor};
+use std::thread;
+
+use llvmlite::{MetadataError, StringError};
+
+const IR: &str = "define i32 @main() {\n  ret i32 0\n}\n";
+
+#[test]
+fn test_parse_assembly_dispose_on_drop() {
+    let lib = common::load();
+    let ctx = lib.create_context();
+
+    let module = ctx.parse_assembly(IR).expect("parse IR");
+    assert_eq!(common::live_modules(), 1);
+
+    drop(module);
+    assert_eq!(common::live_modules(), 0);
+    assert_eq!(common::calls("LLVMPY_DisposeModule"), 1);
+    assert_eq!(common::invalid_frees(), 0);
+}
+
+#[test]
+fn test_parse_assembly_error() {
+    let lib = common::load();
+    let ctx = lib.create_context();
+
+    let ir = "define i32 @main() {\nentry:\n  %x = foo i32 1\n}\n";
+    common::fail_next(
+        "LLVMPY_ParseAssembly",
+        "<string>:3:8: error: expected instruction opcode\n  %x = foo i32 1\n       ^\n",
+    );
+
+    let err = ctx.parse_assembly(ir).err().expect("parse should fail");
+    assert_eq!(err.line, 3);
+    assert_eq!(err.column, 8);
+    assert_eq!(err.message, "expected instruction opcode");
+    assert_eq!(err.source_line(), Some("  %x = foo i32 1"));
+    assert_eq!(
+        err.to_string(),
+        "3:8: error: expected instruction opcode\n  %x = foo i32 1\n       ^",
+    );
+    assert_eq!(common::live_modules(), 0);
+    assert_eq!(common::live_strings(), 0, "error message should be disposed");
+}
+
+#[test]
+fn test_parse_assembly_error_after_multibyte() {
+    let lib = common::load();
+    let ctx = lib.create_context();
+
+    // LLVM counts columns in bytes, `é` takes two.
+    let ir = "@\"é\" = foo i8 0\n";
+    common::fail_next("LLVMPY_ParseAssembly", "<string>:1:9: error: expected 'global'");
+
+    let err = ctx.parse_assembly(ir).err().expect("parse should fail");
+    assert_eq!(err.to_string(), "1:9: error: expected 'global'\n@\"é\" = foo i8 0\n       ^");
+
+    // A column inside a character points at the start of it.
+    common::fail_next("LLVMPY_ParseAssembly", "<string>:1:4: error: bad name");
+    let err = ctx.parse_assembly(ir).err().expect("parse should fail");
+    assert_eq!(err.to_string(), "1:4: error: bad name\n@\"é\" = foo i8 0\n  ^");
+}
+
+#[test]
+fn test_parse_assembly_unlocated_error() {
+    let lib = common::load();
+    let ctx = lib.create_context();
+    common::fail_next("LLVMPY_ParseAssembly", "something went wrong");
+
+    let err = ctx.parse_assembly("nonsense").err().expect("parse should fail");
+    assert_eq!((err.line, err.column), (0, 0));
+    assert_eq!(err.source_line(), None);
+    assert_eq!(err.to_string(), "error: something went wrong");
+}
+
+#[test]
+fn test_parse_assembly_interior_nul() {
+    let lib = common::load();
+    let ctx = lib.create_context();
+
+    let err = ctx.parse_assembly("; header\n\tdefine\0").err().expect("parse should fail");
+    assert_eq!((err.line, err.column), (2, 8));
+    assert_eq!(
+        err.to_string(),
+        "2:8: error: assembly contains a null byte\n\tdefine\0\n\t      ^",
+    );
+    assert_eq!(common::calls("LLVMPY_ParseAssembly"), 0);
+}
+
+#[test]
+fn test_parse_bitcode_error() {
+    let lib = common::load();
+    let ctx = lib.create_context();
+
+    let err = ctx.parse_bitcode(b"not bitcode").err().expect("parse should fail");
+    assert_eq!(err.message, "Invalid bitcode signature");
+    assert_eq!(common::live_strings(), 0);
+}
+
+#[test]
+fn test_bitcode_round_trip() {
+    let lib = common::load();
+    let ctx = lib.create_context();
+    let module = ctx.parse_assembly(IR).expect("parse IR");
+
+    let bitcode = module.to_bitcode();
+    assert!(bitcode.starts_with(b"BC\xC0\xDE"));
+    assert!(bitcode.contains(&0), "bitcode should keep its NUL bytes");
+
+    let parsed = ctx.parse_bitcode(&bitcode).expect("parse bitcode");
+    assert_eq!(parsed.to_bitcode().as_ref(), bitcode.as_ref());
+
+    drop(bitcode);
+    drop(parsed);
+    assert_eq!(common::live_strings(), 0);
+    assert_eq!(common::invalid_frees(), 0);
+}
+
+#[test]
+fn test_bitcode_streaming() {
+    let lib = common::load();
+    let ctx = lib.create_context();
+    let module = ctx.parse_assembly(IR).expect("parse IR");
+
+    let mut file = Vec::new();
+    module.write_bitcode(&mut file).expect("write bitcode");
+    assert_eq!(file, module.to_bitcode().to_vec());
+
+    let parsed = ctx.read_bitcode(Cursor::new(file)).expect("read bitcode");
+    assert_eq!(parsed.to_bitcode().to_vec(), module.to_bitcode().to_vec());
+
+    let err = ctx.read_bitcode(&b"garbage"[..]).err().expect("read should fail");
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert!(err.to_string().contains("Invalid bitcode signature"), "{err}");
+}
+
+#[test]
+fn test_verify_valid_module() {
+    let lib = common::load();
+    let ctx = lib.create_context();
+    let module = ctx.parse_assembly(IR).expect("parse IR");
+
+    module.verify().expect("module should be valid");
+    assert_eq!(common::live_strings(), 0);
+}
+
+#[test]
+fn test_verify_reports_entries() {
+    let lib = common::load();
+    let ctx = lib.create_context();
+    let ir = "\
+declare void @external()
+
+define i32 @first() {
+entry:
+  ret i32 %x
+  %x = add i32 1, 2
+}
+
+define i32 @second() {
+entry:
+  ret i64 0
+}
+";
+    let module = ctx.parse_assembly(ir).expect("parse IR");
+
+    common::fail_next(
+        "LLVMPY_VerifyModule",
+        "Instruction does not dominate all uses!\n\
+         \x20 %x = add i32 1, 2\n\
+         \x20 ret i32 %x\n\
+         Function return type does not match operand type of return inst!\n\
+         \x20 ret i64 0\n\
+         \x20i32\n\
+         Referring to a function in another module!\n\
+         ptr @external\n",
+    );
+    let err = module.verify().expect_err("module should be broken");
+    assert_eq!(common::live_strings(), 0, "verifier output should be disposed");
+
+    assert_eq!(err.entries.len(), 3, "{err}");
+    assert_eq!(err.entries[0].function.as_deref(), Some("first"));
+    assert_eq!(err.entries[0].instruction.as_deref(), Some("%x = add i32 1, 2"));
+    assert_eq!(err.entries[0].message, "Instruction does not dominate all uses!");
+
+    assert_eq!(err.entries[1].function.as_deref(), Some("second"));
+    assert_eq!(err.entries[1].instruction.as_deref(), Some("ret i64 0"));
+
+    assert_eq!(err.entries[2].function.as_deref(), Some("external"));
+    assert_eq!(err.entries[2].instruction, None);
+    assert_eq!(err.entries[2].message, "Referring to a function in another module!");
+}
+
+#[test]
+fn test_module_metadata() {
+    let lib = common::load();
+    let ctx = lib.create_context();
+    let mut module = ctx.parse_assembly(IR).expect("parse IR");
+
+    assert_eq!(module.name(), "<string>");
+    assert_eq!(module.source_file_name(), "<string>");
+    assert_eq!(module.triple(), "");
+    assert_eq!(module.data_layout(), "");
+
+    module.set_name("cached").unwrap();
+    module.set_triple("x86_64-unknown-linux-gnu").unwrap();
+    module.set_data_layout("e-m:e-p270:32:32-i64:64-f80:128-n8:16:32:64-S128").unwrap();
+
+    assert_eq!(module.name(), "cached");
+    assert_eq!(module.triple(), "x86_64-unknown-linux-gnu");
+    assert_eq!(module.data_layout(), "e-m:e-p270:32:32-i64:64-f80:128-n8:16:32:64-S128");
+    assert_eq!(common::live_strings(), 0);
+}
+
+#[test]
+fn test_module_metadata_validation() {
+    let lib = common::load();
+    let ctx = lib.create_context();
+    let mut module = ctx.parse_assembly(IR).expect("parse IR");
+
+    assert!(matches!(
+        module.set_name("a\0b"),
+        Err(StringError::InteriorNul { position: 1 }),
+    ));
+    for triple in ["-pc-linux", "--", "x86 64-pc-linux", "x86_64-pc-linux!"] {
+        let err = module.set_triple(triple).expect_err(triple);
+        assert!(matches!(err, MetadataError::InvalidTriple { .. }), "{err}");
+    }
+    let layouts = [
+        "e--i64:64", "q64", "m:z", "i64:64x", "E1", "i8:9", "i:", "S12", "p:7", "p:64:0",
+        "i64:64:32", "a8:64", "n8:0", "Fi24", "p16777216:64:64", "i64:128:48",
+    ];
+    for layout in layouts {
+        let err = module.set_data_layout(layout).expect_err(layout);
+        assert!(matches!(err, MetadataError::InvalidDataLayout { .. }), "{err}");
+    }
+
+    assert_eq!(common::calls("LLVMPY_SetModuleName"), 0);
+    assert_eq!(common::calls("LLVMPY_SetTarget"), 0);
+    assert_eq!(common::calls("LLVMPY_SetDataLayout"), 0);
+
+    module.set_triple("").unwrap();
+    for triple in [
+        "x86_64",
+        "x86_64--linux",
+        "amdgcn--amdhsa",
+        "nvptx64--",
+        "x86_64-pc-windows-msvc-elf",
+    ] {
+        module.set_triple(triple).expect(triple);
+    }
+    module.set_data_layout("").unwrap();
+    for layout in [
+        "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128",
+        "e-m:o-i64:64-i128:128-n32:64-S128",
+        "E-m:e-p:32:32-Fi8-i64:64-v128:64:128-a:0:32-n32-S64",
+        "e-p:64:64:64:32-i8:8:32-ni:1:2-A5-G1-P0",
+    ] {
+        module.set_data_layout(layout).expect(layout);
+    }
+}
+
+#[test]
+fn test_link_in() {
+    let lib = common::load();
+    let ctx = lib.create_context();
+    let mut prelude = ctx.parse_assembly("declare i32 @helper()\n").expect("parse IR");
+    let user = ctx.parse_assembly(IR).expect("parse IR");
+
+    prelude.link_in(user).expect("link modules");
+    assert_eq!(common::live_modules(), 1, "the source module belongs to LLVM");
+
+    // The stub's bitcode embeds the IR, so the linked function should be in it.
+    let bitcode = prelude.to_bitcode();
+    let needle = b"define i32 @main";
+    assert!(bitcode.windows(needle.len()).any(|w| w == needle));
+    drop((bitcode, prelude));
+    assert_eq!(common::live_modules(), 0);
+    assert_eq!(common::invalid_frees(), 0);
+}
+
+#[test]
+fn test_link_in_error() {
+    let lib = common::load();
+    let ctx = lib.create_context();
+    let mut prelude = ctx.parse_assembly(IR).expect("parse IR");
+    let user = ctx.parse_assembly(IR).expect("parse IR");
+
+    let message = "Linking globals named 'main': symbol multiply defined!";
+    common::fail_next("LLVMPY_LinkModules", message);
+
+    let err = prelude.link_in(user).expect_err("link should fail");
+    assert_eq!(err.message, message);
+    assert_eq!(common::live_modules(), 1);
+    assert_eq!(common::live_strings(), 0);
+    drop(prelude);
+    assert_eq!(common::invalid_frees(), 0);
+}
+
+#[test]
+fn test_link_in_different_contexts() {
+    let lib = common::load();
+    let ctx = lib.create_context();
+    let other_ctx = lib.create_context();
+    let mut module = ctx.parse_assembly(IR).expect("parse IR");
+    let other = other_ctx.parse_assembly(IR).expect("parse IR");
+
+    let err = module.link_in(other).expect_err("link should fail");
+    assert!(err.message.contains("different contexts"), "{err}");
+    assert_eq!(common::calls("LLVMPY_LinkModules"), 0);
+    assert_eq!(common::live_modules(), 1, "the rejected module is still disposed");
+}
+
+#[test]
+fn test_clone_is_independent() {
+    let lib = common::load();
+    let ctx = lib.create_context();
+    let original = ctx.parse_assembly(IR).expect("parse IR");
+
+    let mut copy = original.clone();
+    assert_eq!(common::live_modules(), 2);
+    assert_eq!(copy.to_bitcode().to_vec(), original.to_bitcode().to_vec());
+
+    copy.set_name("optimized").unwrap();
+    copy.set_triple("x86_64-unknown-linux-gnu").unwrap();
+    copy.link_in(ctx.parse_assembly("declare void @extra()\n").unwrap()).unwrap();
+
+    assert_eq!(original.name(), "<string>");
+    assert_eq!(original.triple(), "");
+    assert_ne!(copy.to_bitcode().to_vec(), original.to_bitcode().to_vec());
+
+    drop(copy);
+    assert_eq!(common::live_modules(), 1);
+    assert_eq!(original.name(), "<string>");
+    drop(original);
+    assert_eq!(common::live_modules(), 0);
+    assert_eq!(common::invalid_frees(), 0);
+}
+
+#[test]
+fn test_context_moves_between_threads() {
+    let lib = common::load();
+    let ctx = lib.create_context();
+
+    thread::spawn(move || {
+        let module = ctx.parse_assembly(IR).expect("parse IR");
+        drop(module);
+        drop(ctx);
+        assert_eq!(common::invalid_frees(), 0);
+    })
+    .join()
+    .unwrap();
+}
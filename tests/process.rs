@@ -0,0 +1,28 @@
+mod common;
+
+use libloading::Library;
+use llvmlite::LLVMLite;
+
+#[test]
+fn test_from_library() {
+    common::reset();
+    let library = unsafe { Library::new(common::path()) }.unwrap();
+    let lib = unsafe { LLVMLite::from_library(library) }.expect("stub should load");
+
+    let s = lib.create_string("attached");
+    assert_eq!(&*s, "attached");
+}
+
+// Kept in its own test binary as it makes the stub's symbols globally visible.
+#[cfg(unix)]
+#[test]
+fn test_from_current_process() {
+    use libloading::os::unix::{Library, RTLD_GLOBAL, RTLD_NOW};
+
+    common::reset();
+    let _global = unsafe { Library::open(Some(common::path()), RTLD_NOW | RTLD_GLOBAL) }
+        .expect("stub should load globally");
+
+    let lib = unsafe { LLVMLite::from_current_process() }.expect("symbols should resolve");
+    assert_eq!(lib.llvm_version().major, 14);
+}
@@ -0,0 +1,98 @@
+mod common;
+
+use std::ffi::CString;
+use std::thread;
+
+use llvmlite::StringError;
+
+#[test]
+fn test_create_string() {
+    let lib = common::load();
+
+    let s = lib.create_string("hello, world");
+    assert_eq!(&*s, "hello, world");
+    assert_eq!(common::live_strings(), 1);
+
+    drop(s);
+    assert_eq!(common::live_strings(), 0);
+    assert_eq!(common::calls("LLVMPY_DisposeString"), 1);
+    assert_eq!(common::invalid_frees(), 0);
+}
+
+#[test]
+fn test_create_byte_string() {
+    let lib = common::load();
+
+    let s = lib.create_byte_string(CString::new(vec![0xFF, 0xFE, b'a']).unwrap());
+    assert_eq!(s.to_bytes(), &[0xFF, 0xFE, b'a']);
+
+    drop(s);
+    assert_eq!(common::live_strings(), 0);
+    assert_eq!(common::invalid_frees(), 0);
+}
+
+#[test]
+fn test_try_create_string_interior_nul() {
+    let lib = common::load();
+
+    let err = lib.try_create_string("abc\0def").unwrap_err();
+    assert!(matches!(err, StringError::InteriorNul { position: 3 }), "{err}");
+    assert_eq!(common::calls("LLVMPY_CreateString"), 0);
+
+    let err = lib.try_create_byte_string(b"\xFF\0".to_vec()).unwrap_err();
+    assert!(matches!(err, StringError::InteriorNul { position: 1 }), "{err}");
+}
+
+#[test]
+fn test_try_create_string_invalid_utf8() {
+    let lib = common::load();
+    common::fail_next("LLVMPY_CreateString", "");
+
+    let err = lib.try_create_string("hello").unwrap_err();
+    assert!(matches!(err, StringError::InvalidUtf8), "{err}");
+    assert_eq!(common::live_strings(), 0);
+    assert_eq!(common::invalid_frees(), 0);
+}
+
+#[test]
+#[should_panic(expected = "interior_nul")]
+fn test_create_string_panics_on_nul() {
+    let lib = common::load();
+    lib.create_string("abc\0");
+}
+
+#[test]
+fn test_create_bytes_keeps_nuls() {
+    let lib = common::load();
+
+    let bytes = lib.create_bytes(b"BC\xC0\xDE\0\0\0\0tail");
+    assert_eq!(&*bytes, b"BC\xC0\xDE\0\0\0\0tail");
+    assert_eq!(bytes.len(), 12);
+    assert_eq!(common::live_strings(), 1);
+
+    drop(bytes);
+    assert_eq!(common::live_strings(), 0);
+    assert_eq!(common::calls("LLVMPY_DisposeString"), 1);
+    assert_eq!(common::invalid_frees(), 0);
+}
+
+#[test]
+fn test_string_outlives_library_handle() {
+    let lib = common::load();
+    let s = lib.create_string("kept alive");
+    drop(lib);
+
+    assert_eq!(&*s, "kept alive");
+    drop(s);
+    assert_eq!(common::live_strings(), 0);
+}
+
+#[test]
+fn test_string_send_to_thread() {
+    let lib = common::load();
+    let s = lib.create_string("moved");
+
+    let len = thread::spawn(move || s.len()).join().unwrap();
+    assert_eq!(len, 5);
+    assert_eq!(common::calls("LLVMPY_DisposeString"), 0);
+}
@@ -0,0 +1,210 @@
+mod common;
+
+use llvmlite::{LLVMOpcode, LLVMValueKind, Opcode, Value};
+
+const IR: &str = "\
+@counter = global i32 0
+@errno = external global i32
+
+declare i32 @puts(ptr)
+
+define i32 @main() {
+entry:
+  ret i32 0
+}
+";
+
+#[test]
+fn test_function_downcasts() {
+    let lib = common::load();
+    let ctx = lib.create_context();
+    let module = ctx.parse_assembly(IR).expect("parse IR");
+
+    let main = module.get_function("main").unwrap().unwrap();
+    let value = Value::from(main);
+    assert_eq!(value.kind(), Some(LLVMValueKind::LLVMFunctionValueKind));
+    assert_eq!(value.name(), "main");
+
+    let function = value.as_function().expect("main is a function");
+    assert_eq!(function.name(), "main");
+    assert!(value.as_global_variable().is_none());
+    assert!(value.as_basic_block().is_none());
+    assert!(value.as_instruction().is_none());
+    assert!(value.as_argument().is_none());
+}
+
+#[test]
+fn test_global_downcasts() {
+    let lib = common::load();
+    let ctx = lib.create_context();
+    let module = ctx.parse_assembly(IR).expect("parse IR");
+
+    let counter = module.get_global("counter").unwrap().unwrap();
+    assert_eq!(counter.kind(), Some(LLVMValueKind::LLVMGlobalVariableValueKind));
+    assert!(counter.as_function().is_none());
+    assert_eq!(counter.as_global_variable().unwrap().name(), "counter");
+}
+
+#[test]
+fn test_is_declaration() {
+    let lib = common::load();
+    let ctx = lib.create_context();
+    let module = ctx.parse_assembly(IR).expect("parse IR");
+
+    let declared: Vec<_> = module
+        .functions()
+        .map(|f| (f.name().into_owned(), f.is_declaration()))
+        .collect();
+    assert_eq!(declared, [("puts".to_string(), true), ("main".to_string(), false)]);
+
+    assert!(!module.get_global("counter").unwrap().unwrap().is_declaration());
+    assert!(module.get_global("errno").unwrap().unwrap().is_declaration());
+}
+
+#[test]
+fn test_value_kind_from_raw() {
+    assert_eq!(LLVMValueKind::from_raw(0), Some(LLVMValueKind::LLVMArgumentValueKind));
+    assert_eq!(LLVMValueKind::from_raw(24), Some(LLVMValueKind::LLVMInstructionValueKind));
+    assert_eq!(LLVMValueKind::from_raw(26), None);
+    assert_eq!(LLVMValueKind::from_raw(-1), None);
+}
+
+const BODY: &str = "\
+@counter = global i32 0
+
+declare i32 @puts(ptr)
+
+define i32 @bump(i32 %by, ptr %msg) {
+entry:
+  %old = load i32, ptr @counter, align 4
+  %new = add i32 %old, %by
+  store i32 %new, ptr @counter, align 4
+  %printed = call i32 @puts(ptr %msg)
+  br label %done
+
+done:                                             ; preds = %entry
+  ret i32 %new
+}
+";
+
+#[test]
+fn test_arguments() {
+    let lib = common::load();
+    let ctx = lib.create_context();
+    let module = ctx.parse_assembly(BODY).expect("parse IR");
+
+    let bump = module.get_function("bump").unwrap().unwrap();
+    let names: Vec<_> = bump.arguments().map(|a| a.name().into_owned()).collect();
+    assert_eq!(names, ["by", "msg"]);
+
+    let puts = module.get_function("puts").unwrap().unwrap();
+    let arguments: Vec<_> = puts.arguments().collect();
+    assert_eq!(arguments.len(), 1);
+    assert_eq!(arguments[0].name(), "");
+    assert_eq!(common::live_iterators(), 0);
+}
+
+#[test]
+fn test_blocks_and_instructions() {
+    let lib = common::load();
+    let ctx = lib.create_context();
+    let module = ctx.parse_assembly(BODY).expect("parse IR");
+
+    let bump = module.get_function("bump").unwrap().unwrap();
+    let blocks: Vec<_> = bump.blocks().collect();
+    let names: Vec<_> = blocks.iter().map(|b| b.name().into_owned()).collect();
+    assert_eq!(names, ["entry", "done"]);
+
+    let counts: Vec<_> = blocks.iter().map(|b| b.instructions().count()).collect();
+    assert_eq!(counts, [5, 1]);
+
+    let names: Vec<_> = blocks[0].instructions().map(|i| i.name().into_owned()).collect();
+    assert_eq!(names, ["old", "new", "", "printed", ""]);
+
+    let puts = module.get_function("puts").unwrap().unwrap();
+    assert_eq!(puts.blocks().count(), 0);
+    assert_eq!(common::live_iterators(), 0);
+    assert_eq!(common::calls("LLVMPY_DisposeInstructionsIter"), 3);
+}
+
+#[test]
+fn test_operands() {
+    let lib = common::load();
+    let ctx = lib.create_context();
+    let module = ctx.parse_assembly(BODY).expect("parse IR");
+
+    let bump = module.get_function("bump").unwrap().unwrap();
+    let entry = bump.blocks().next().unwrap();
+    let operands: Vec<_> = entry
+        .instructions()
+        .map(|i| {
+            i.operands()
+                .map(|o| (o.name().into_owned(), o.kind().unwrap()))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    use LLVMValueKind::*;
+    let owned = |pairs: &[(&str, LLVMValueKind)]| {
+        pairs.iter().map(|(n, k)| (n.to_string(), *k)).collect::<Vec<_>>()
+    };
+    assert_eq!(operands[0], owned(&[("counter", LLVMGlobalVariableValueKind)]));
+    assert_eq!(
+        operands[1],
+        owned(&[("old", LLVMInstructionValueKind), ("by", LLVMArgumentValueKind)])
+    );
+    assert_eq!(
+        operands[2],
+        owned(&[("new", LLVMInstructionValueKind), ("counter", LLVMGlobalVariableValueKind)])
+    );
+    assert_eq!(
+        operands[3],
+        owned(&[("msg", LLVMArgumentValueKind), ("puts", LLVMFunctionValueKind)]),
+        "the callee should be the last operand"
+    );
+    assert_eq!(operands[4], owned(&[("done", LLVMBasicBlockValueKind)]));
+
+    // Operands keep the module borrowed, not the iterators that produced them.
+    let by = operands[1][1].0.clone();
+    let argument = bump.arguments().find(|a| a.name() == by).unwrap();
+    assert!(Value::from(argument).as_argument().is_some());
+    assert_eq!(common::live_iterators(), 0);
+}
+
+#[test]
+fn test_opcodes() {
+    let lib = common::load();
+    let ctx = lib.create_context();
+    let module = ctx.parse_assembly(BODY).expect("parse IR");
+
+    let bump = module.get_function("bump").unwrap().unwrap();
+    let opcodes: Vec<_> = bump
+        .blocks()
+        .flat_map(|b| b.instructions().collect::<Vec<_>>())
+        .map(|i| i.opcode())
+        .collect();
+
+    use LLVMOpcode::*;
+    let expected = [LLVMLoad, LLVMAdd, LLVMStore, LLVMCall, LLVMBr, LLVMRet];
+    assert_eq!(opcodes, expected.map(Opcode::Known));
+    assert_eq!(common::live_strings(), 0, "opcode names should be disposed");
+
+    let terminators: Vec<_> = opcodes.iter().map(Opcode::is_terminator).collect();
+    assert_eq!(terminators, [false, false, false, false, true, true]);
+    let calls: Vec<_> = opcodes.iter().map(Opcode::is_call).collect();
+    assert_eq!(calls, [false, false, false, true, false, false]);
+    let accesses: Vec<_> = opcodes.iter().map(Opcode::is_memory_access).collect();
+    assert_eq!(accesses, [true, false, true, false, false, false]);
+}
+
+#[test]
+fn test_opcode_from_name() {
+    assert_eq!("getelementptr".parse(), Ok(Opcode::Known(LLVMOpcode::LLVMGetElementPtr)));
+    assert_eq!("cmpxchg".parse(), Ok(Opcode::Known(LLVMOpcode::LLVMAtomicCmpXchg)));
+    assert_eq!("va_arg".parse::<Opcode>().unwrap(), LLVMOpcode::LLVMVAArg);
+
+    let unknown: Opcode = "frobnicate".parse().unwrap();
+    assert_eq!(unknown, Opcode::Unknown("frobnicate".to_string()));
+    assert_eq!(unknown.known(), None);
+    assert!(!unknown.is_terminator() && !unknown.is_call() && !unknown.is_memory_access());
+}